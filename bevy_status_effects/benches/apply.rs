@@ -0,0 +1,71 @@
+//! Benchmarks for applying effects to a target that already has many effects.
+
+// `criterion_group!` generates an undocumented public function.
+#![allow(missing_docs)]
+
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+#[derive(StatusEffect, Component, Default)]
+struct Stacked;
+
+#[derive(StatusEffect, Component, Default)]
+struct Replaced;
+
+/// Applies a replacing effect to a target that has a growing number of stacked effects.
+/// The cost should stay flat, since only effects of the same type are visited.
+fn apply_replace(c: &mut Criterion) {
+    let mut group = c.benchmark_group("apply_replace");
+
+    for stacks in [0, 10, 100, 1000] {
+        let mut world = World::new();
+        init_effect_hook::<Stacked>(&mut world);
+        init_effect_hook::<Replaced>(&mut world);
+
+        let target = world.spawn_empty().id();
+        for _ in 0..stacks {
+            world.spawn((Effecting(target), Stacked));
+        }
+        world.spawn((Effecting(target), EffectMode::Replace, Replaced));
+        world.flush();
+
+        group.bench_with_input(BenchmarkId::from_parameter(stacks), &target, |b, target| {
+            b.iter(|| {
+                world.spawn((Effecting(black_box(*target)), EffectMode::Replace, Replaced));
+                world.flush();
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Applies a stacking effect to a target that has a growing number of stacked effects.
+fn apply_stack(c: &mut Criterion) {
+    let mut group = c.benchmark_group("apply_stack");
+
+    for stacks in [0, 10, 100, 1000] {
+        let mut world = World::new();
+        init_effect_hook::<Stacked>(&mut world);
+
+        let target = world.spawn_empty().id();
+        for _ in 0..stacks {
+            world.spawn((Effecting(target), Stacked));
+        }
+        world.flush();
+
+        group.bench_with_input(BenchmarkId::from_parameter(stacks), &target, |b, target| {
+            b.iter(|| {
+                let effect = world.spawn((Effecting(black_box(*target)), Stacked)).id();
+                world.despawn(effect);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, apply_replace, apply_stack);
+criterion_main!(benches);
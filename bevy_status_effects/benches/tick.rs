@@ -0,0 +1,94 @@
+//! Benchmarks for ticking a large number of effect timers.
+
+// `criterion_group!` generates an undocumented public function.
+#![allow(missing_docs)]
+
+use bevy_app::{App, PreUpdate, Update};
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use bevy_time::Time;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::time::Duration;
+
+const COUNTS: [usize; 3] = [1_000, 10_000, 100_000];
+
+fn app(count: usize) -> App {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::default());
+    app.init_resource::<Time>();
+
+    app.world_mut().spawn_batch((0..count).map(|_| {
+        (
+            Lifetime::from_seconds(1_000_000.0),
+            Delay::from_seconds(1.0),
+        )
+    }));
+
+    app
+}
+
+fn advance(app: &mut App) {
+    app.world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_millis(16));
+}
+
+/// Ticks timers using the plugin's systems, which run in `PreUpdate`.
+fn tick_parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tick_parallel");
+
+    for count in COUNTS {
+        let mut app = app(count);
+
+        group.bench_function(BenchmarkId::from_parameter(count), |b| {
+            b.iter(|| {
+                advance(&mut app);
+                app.world_mut().run_schedule(PreUpdate);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// The serial implementation that the plugin used to use, for comparison.
+fn serial_timers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut lifetimes: Query<(Entity, &mut Lifetime)>,
+    mut delays: Query<&mut Delay>,
+) {
+    for (entity, mut lifetime) in &mut lifetimes {
+        lifetime.timer.tick(time.delta());
+
+        if lifetime.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for mut delay in &mut delays {
+        delay.timer.tick(time.delta());
+    }
+}
+
+/// Ticks timers using [`serial_timers`], which runs in `Update` so that it's scheduled the same way as the plugin's systems.
+fn tick_serial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tick_serial");
+
+    for count in COUNTS {
+        let mut app = app(count);
+        app.add_systems(Update, serial_timers);
+
+        group.bench_function(BenchmarkId::from_parameter(count), |b| {
+            b.iter(|| {
+                advance(&mut app);
+                app.world_mut().run_schedule(Update);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, tick_parallel, tick_serial);
+criterion_main!(benches);
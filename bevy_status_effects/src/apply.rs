@@ -0,0 +1,274 @@
+use crate::error::StatusEffectError;
+use crate::hook::effect_keys;
+use crate::id::EffectRegistry;
+use crate::index::EffectKey;
+use crate::relation::{EffectedBy, Effecting};
+use crate::snapshot::take_snapshots;
+use crate::{EffectMode, ReflectComponent};
+use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
+use bevy_reflect::prelude::ReflectDefault;
+use bevy_time::{Time, Timer, TimerMode};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tracing::debug_span;
+
+/// Prevents effects with the given keys from being [applied](apply_effect) to the entity.
+#[derive(Component, Reflect, Eq, PartialEq, Debug, Default, Clone)]
+#[reflect(Component, PartialEq, Debug, Default, Clone)]
+pub struct EffectImmunity(pub HashSet<EffectKey>);
+
+/// The maximum number of [stacked](EffectMode::Stack) effects with the same key that can be [applied](apply_effect) to the entity.
+///
+/// Effects that [replace](EffectMode::Replace) each other don't count towards the limit.
+#[derive(Component, Reflect, Eq, PartialEq, Debug, Copy, Clone)]
+#[reflect(Component, PartialEq, Debug, Clone)]
+pub struct EffectStackLimit(pub usize);
+
+/// Prevents effects with the given keys from being [applied](apply_effect) to the entity until their timer finishes.
+///
+/// Cooldowns are ticked alongside effect timers, and removed once they finish.
+#[derive(Component, Reflect, Eq, PartialEq, Debug, Default, Clone)]
+#[reflect(Component, PartialEq, Debug, Default, Clone)]
+pub struct EffectCooldowns(pub HashMap<EffectKey, Timer>);
+
+impl EffectCooldowns {
+    /// Starts a cooldown for the effect, replacing any existing cooldown.
+    pub fn start(&mut self, key: EffectKey, duration: Duration) {
+        self.0.insert(key, Timer::new(duration, TimerMode::Once));
+    }
+
+    /// Returns the time remaining until the effect can be applied again, if it is on cooldown.
+    pub fn remaining(&self, key: &EffectKey) -> Option<Duration> {
+        self.0
+            .get(key)
+            .filter(|timer| !timer.finished())
+            .map(Timer::remaining)
+    }
+}
+
+/// Triggered on the target before an effect is applied to it, which allows observers to modify or [cancel](Self::cancel) the effect.
+///
+/// The effect entity has been spawned, but isn't [effecting](Effecting) the target yet,
+/// so its components (such as its [`Lifetime`](crate::Lifetime), [`EffectMode`], and data) can be freely changed.
+/// Any commands queued by the observers are applied before the effect is applied.
+///
+/// Triggered after the target has [accepted](can_apply_effect) the effect, for every API that applies an effect,
+/// such as [`apply_effect`], [`apply_effect_batch`](crate::apply_effect_batch), [`spawn_effect_by_name`](crate::spawn_effect_by_name),
+/// and [`transfer_effect`](crate::transfer_effect).
+/// A [moved](crate::TransferMode::Move) effect is still effecting its old target when this is triggered.
+/// The only exception is [`load_effect`](crate::load_effect), since loaded effects were already applied before they were saved.
+#[derive(Event, Eq, PartialEq, Debug, Clone)]
+pub struct ApplyingEffect {
+    /// The incoming effect entity.
+    pub effect: Entity,
+    cancelled: Option<Cow<'static, str>>,
+}
+
+impl ApplyingEffect {
+    /// Prevents the effect from being applied, and despawns it.
+    /// A [moved](crate::TransferMode::Move) effect is instead left on its old target.
+    /// The reason is returned in [`StatusEffectError::Cancelled`].
+    pub fn cancel(&mut self, reason: impl Into<Cow<'static, str>>) {
+        self.cancelled = Some(reason.into());
+    }
+
+    /// Returns true if an observer has cancelled the effect.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.is_some()
+    }
+}
+
+/// Spawns the effect bundle, which is [effecting](Effecting) the target, returning the new effect entity.
+///
+/// The bundle shouldn't contain [`Effecting`], since it is added once the effect has been accepted.
+/// If the effect has an [`EffectSource`](crate::EffectSource), its [snapshots](crate::Snapshot) are taken,
+/// and then [`ApplyingEffect`] is triggered on the target before the effect is applied.
+///
+/// # Errors
+/// Returns why the effect was rejected, if the target doesn't exist, [can't accept](can_apply_effect) the effect,
+/// or an observer cancelled it. Rejected effects are despawned.
+pub fn apply_effect(
+    world: &mut World,
+    target: Entity,
+    bundle: impl Bundle,
+) -> Result<Entity, StatusEffectError> {
+    if world.get_entity(target).is_err() {
+        return Err(StatusEffectError::InvalidTarget(target));
+    }
+
+    let effect = world.spawn(bundle).id();
+    accept_effect(world, effect, target)?;
+    Ok(effect)
+}
+
+/// Makes an existing effect entity [effect](Effecting) the target if it is accepted, or despawns it.
+///
+/// Every public API that applies a new effect goes through this, so they all check the target,
+/// take [snapshots](crate::Snapshot), and trigger [`ApplyingEffect`].
+pub(crate) fn accept_effect(
+    world: &mut World,
+    effect: Entity,
+    target: Entity,
+) -> Result<(), StatusEffectError> {
+    let result = can_apply_effect(world, effect, target).and_then(|()| {
+        take_snapshots(world, effect);
+        trigger_applying_effect(world, effect, target, ())
+    });
+
+    if result.is_err() {
+        world.try_despawn(effect).ok();
+    }
+
+    result
+}
+
+/// Triggers [`ApplyingEffect`] on the target, and then inserts [`Effecting`] along with the bundle, unless it was cancelled.
+///
+/// Unlike [`accept_effect`], the effect is left as it was if it is cancelled, so that it can be used for effects that already exist.
+pub(crate) fn trigger_applying_effect(
+    world: &mut World,
+    effect: Entity,
+    target: Entity,
+    bundle: impl Bundle,
+) -> Result<(), StatusEffectError> {
+    let mut event = ApplyingEffect {
+        effect,
+        cancelled: None,
+    };
+    world.trigger_targets_ref(&mut event, target);
+    world.flush();
+
+    if let Some(reason) = event.cancelled {
+        return Err(StatusEffectError::Cancelled { target, reason });
+    }
+
+    if world.get_entity(effect).is_err() {
+        return Err(StatusEffectError::Cancelled {
+            target,
+            reason: "the effect was despawned".into(),
+        });
+    }
+
+    if world.get_entity(target).is_err() {
+        return Err(StatusEffectError::InvalidTarget(target));
+    }
+
+    world.entity_mut(effect).insert((bundle, Effecting(target)));
+    Ok(())
+}
+
+/// Checks whether the effect can be applied to the target,
+/// based on the target's [`EffectImmunity`], [`EffectCooldowns`], and [`EffectStackLimit`].
+///
+/// # Errors
+/// Returns why the effect would be rejected.
+pub fn can_apply_effect(
+    world: &World,
+    effect: Entity,
+    target: Entity,
+) -> Result<(), StatusEffectError> {
+    let Ok(effect) = world.get_entity(effect) else {
+        return Err(StatusEffectError::InvalidEffect(effect));
+    };
+
+    let Ok(target_ref) = world.get_entity(target) else {
+        return Err(StatusEffectError::InvalidTarget(target));
+    };
+
+    if effect.id() == target {
+        return Err(StatusEffectError::InvalidTarget(target));
+    }
+
+    let registry = world.get_resource::<EffectRegistry>();
+    let stacked = effect.get::<EffectMode>() != Some(&EffectMode::Replace);
+
+    for key in effect_keys(registry, effect) {
+        check_effect_key(target_ref, &key)?;
+
+        if stacked && let Some(limit) = target_ref.get::<EffectStackLimit>() {
+            let stacks = target_ref
+                .get::<EffectedBy>()
+                .into_iter()
+                .flatten()
+                .filter_map(|entity| world.get_entity(*entity).ok())
+                .filter(|entity| entity.get::<EffectMode>() != Some(&EffectMode::Replace))
+                .filter(|entity| effect_keys(registry, *entity).contains(&key))
+                .count();
+
+            if stacks >= limit.0 {
+                return Err(StatusEffectError::StackLimit {
+                    target,
+                    key,
+                    limit: limit.0,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the target's [`EffectImmunity`] and [`EffectCooldowns`] for the key.
+pub(crate) fn check_effect_key(
+    target: EntityRef,
+    key: &EffectKey,
+) -> Result<(), StatusEffectError> {
+    if let Some(immunity) = target.get::<EffectImmunity>()
+        && immunity.0.contains(key)
+    {
+        return Err(StatusEffectError::Immune {
+            target: target.id(),
+            key: key.clone(),
+        });
+    }
+
+    if let Some(remaining) = target
+        .get::<EffectCooldowns>()
+        .and_then(|cooldowns| cooldowns.remaining(key))
+    {
+        return Err(StatusEffectError::Cooldown {
+            target: target.id(),
+            key: key.clone(),
+            remaining,
+        });
+    }
+
+    Ok(())
+}
+
+/// A [`Command`] that applies an effect to the target using [`apply_effect`].
+pub struct ApplyEffect<B> {
+    /// The entity that the effect will be effecting.
+    pub target: Entity,
+    /// The effect bundle.
+    pub bundle: B,
+}
+
+impl<B: Bundle> Command<Result> for ApplyEffect<B> {
+    fn apply(self, world: &mut World) -> Result {
+        apply_effect(world, self.target, self.bundle)?;
+        Ok(())
+    }
+}
+
+pub(super) fn tick_effect_cooldowns<C: Default + Send + Sync + 'static>(
+    time: Res<Time<C>>,
+    mut query: Query<&mut EffectCooldowns>,
+) {
+    let _span = debug_span!("tick_effect_cooldowns").entered();
+
+    let delta = time.delta();
+    if delta.is_zero() {
+        return;
+    }
+
+    for mut cooldowns in &mut query {
+        if cooldowns.0.is_empty() {
+            continue;
+        }
+
+        cooldowns.0.retain(|_, timer| !timer.tick(delta).finished());
+    }
+}
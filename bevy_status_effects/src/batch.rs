@@ -0,0 +1,120 @@
+use crate::apply::{accept_effect, check_effect_key};
+use crate::error::StatusEffectError;
+use crate::hook::component_effect_keys;
+use crate::id::EffectRegistry;
+use crate::relation::Effecting;
+use bevy_ecs::bundle::NoBundleEffect;
+use bevy_ecs::component::ComponentId;
+use bevy_ecs::prelude::*;
+use std::collections::HashSet;
+
+/// The result of applying an effect to many targets using [`apply_effect_batch`].
+#[derive(Eq, PartialEq, Debug, Default, Clone)]
+pub struct EffectBatch {
+    /// The targets that accepted the effect, along with the effect entity that was spawned for each of them.
+    ///
+    /// Only effects that are still effecting their target once the batch has been applied are included.
+    pub accepted: Vec<(Entity, Entity)>,
+    /// The targets that didn't accept the effect, along with the reason it was rejected.
+    pub rejected: Vec<(Entity, StatusEffectError)>,
+}
+
+impl EffectBatch {
+    /// Returns an iterator over the targets that accepted the effect.
+    pub fn targets(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.accepted.iter().map(|(target, _)| *target)
+    }
+
+    /// Returns an iterator over the spawned effect entities.
+    pub fn effects(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.accepted.iter().map(|(_, effect)| *effect)
+    }
+}
+
+/// Spawns a copy of the effect bundle for each target, all at once, and then applies each of them the same way as [`apply_effect`](crate::apply_effect).
+///
+/// The bundle shouldn't contain [`Effecting`], since it is added for each target.
+/// Before anything is spawned, each target is checked for [immunity](crate::EffectImmunity) and [cooldowns](crate::EffectCooldowns)
+/// using the effect types in the bundle, so rejected targets don't spawn an effect.
+/// Each spawned effect then has its [snapshots](crate::Snapshot) taken, and [`ApplyingEffect`](crate::ApplyingEffect) is triggered on its target.
+/// The remaining checks, such as the [stack limit](crate::EffectStackLimit) or a [`DynamicEffect`](crate::DynamicEffect)'s kind,
+/// are made once the effect has been spawned.
+///
+/// Effects are still replaced and have their timers merged per target, according to their [`EffectMode`](crate::EffectMode).
+/// If the same target is given more than once, each copy counts towards its stack limit,
+/// and only the effects that are still alive afterwards are [accepted](EffectBatch::accepted).
+pub fn apply_effect_batch<B>(
+    world: &mut World,
+    bundle: B,
+    targets: impl IntoIterator<Item = Entity>,
+) -> EffectBatch
+where
+    B: Bundle<Effect: NoBundleEffect> + Clone,
+{
+    let components: HashSet<ComponentId> = world
+        .register_bundle::<B>()
+        .iter_contributed_components()
+        .collect();
+    let keys = component_effect_keys(world.get_resource::<EffectRegistry>(), |id| {
+        components.contains(&id)
+    });
+
+    let mut targets_accepted = Vec::new();
+    let mut rejected = Vec::new();
+
+    for target in targets {
+        let checked = match world.get_entity(target) {
+            Ok(target_ref) => keys
+                .iter()
+                .try_for_each(|key| check_effect_key(target_ref, key)),
+            Err(_) => Err(StatusEffectError::InvalidTarget(target)),
+        };
+
+        match checked {
+            Ok(()) => targets_accepted.push(target),
+            Err(error) => rejected.push((target, error)),
+        }
+    }
+
+    let effects: Vec<Entity> = world
+        .spawn_batch(targets_accepted.iter().map(|_| bundle.clone()))
+        .collect();
+
+    let mut accepted = Vec::new();
+    for (target, effect) in targets_accepted.into_iter().zip(effects) {
+        match accept_effect(world, effect, target) {
+            Ok(()) => accepted.push((target, effect)),
+            Err(error) => rejected.push((target, error)),
+        }
+    }
+    world.flush();
+
+    // Effects may have been replaced by a later copy for the same target, or despawned by an observer.
+    accepted
+        .retain(|(target, effect)| world.get::<Effecting>(*effect) == Some(&Effecting(*target)));
+
+    EffectBatch { accepted, rejected }
+}
+
+/// A [`Command`] that applies an effect to many targets using [`apply_effect_batch`],
+/// and then triggers [`EffectBatchApplied`] with the result.
+pub struct ApplyEffectBatch<B> {
+    /// The effect bundle, which is cloned for each target.
+    pub bundle: B,
+    /// The entities that the effect will be effecting.
+    pub targets: Vec<Entity>,
+}
+
+impl<B> Command for ApplyEffectBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect> + Clone,
+{
+    fn apply(self, world: &mut World) {
+        let batch = apply_effect_batch(world, self.bundle, self.targets);
+        world.trigger(EffectBatchApplied(batch));
+    }
+}
+
+/// Triggered after an [`ApplyEffectBatch`] command has been applied.
+#[derive(Event, Eq, PartialEq, Debug, Clone)]
+pub struct EffectBatchApplied(pub EffectBatch);
@@ -0,0 +1,483 @@
+use crate::apply::accept_effect;
+use crate::error::StatusEffectError;
+use crate::reflect::check_target;
+use crate::save::ComponentsDeserializer;
+use crate::{
+    Delay, EffectMode, EffectTimer, Lifetime, ReflectComponent, ReflectDefault,
+    StatusEffectSystems, TimerMergeMode,
+};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::io::Reader;
+use bevy_asset::{Asset, AssetApp, AssetEvent, AssetLoader, Assets, Handle, LoadContext};
+use bevy_ecs::intern::Interned;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_ecs::schedule::ScheduleLabel;
+use bevy_reflect::{PartialReflect, Reflect, TypePath, TypeRegistry, TypeRegistryArc};
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use serde::de::{DeserializeSeed, Error as _, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::Formatter;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Adds the [`EffectDefinition`] asset and its loader.
+pub struct EffectDefinitionPlugin {
+    /// If true, effects that were spawned from a definition are updated when the definition is modified,
+    /// such as when it is hot-reloaded.
+    pub update_active: bool,
+    /// The schedule that active effects are updated in, which should match the [`StatusEffectPlugin`](crate::StatusEffectPlugin)'s.
+    /// Defaults to [`PreUpdate`].
+    pub schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl EffectDefinitionPlugin {
+    /// Creates a plugin that updates active effects in the given schedule.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            update_active: false,
+            schedule: schedule.intern(),
+        }
+    }
+
+    /// A builder that overwrites whether active effects are updated with a new value.
+    pub fn with_update_active(mut self, update_active: bool) -> Self {
+        self.update_active = update_active;
+        self
+    }
+}
+
+impl Default for EffectDefinitionPlugin {
+    fn default() -> Self {
+        Self::new(PreUpdate)
+    }
+}
+
+impl Plugin for EffectDefinitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EffectTags>()
+            .register_type::<EffectDefinitionHandle>()
+            .init_asset::<EffectDefinition>()
+            .init_asset_loader::<EffectDefinitionLoader>();
+
+        if self.update_active {
+            app.add_systems(
+                self.schedule,
+                update_active_definitions.before(StatusEffectSystems::TickTimers),
+            );
+        }
+    }
+}
+
+/// A data-driven status effect, which is usually loaded from a `.effect.ron` file.
+///
+/// ```ron
+/// (
+///     name: "Burning",
+///     mode: Replace,
+///     lifetime: Some((seconds: 5.0, mode: Some(Max))),
+///     delay: Some((seconds: 1.0)),
+///     tags: ["fire"],
+///     components: {
+///         "my_game::Burning": (damage: 5),
+///     },
+/// )
+/// ```
+#[derive(Asset, TypePath, Debug)]
+pub struct EffectDefinition {
+    /// The name of the effect.
+    pub name: String,
+    /// The mode used when applying the effect.
+    pub mode: EffectMode,
+    /// The [`Lifetime`] of the effect, if it has one.
+    pub lifetime: Option<TimerDefinition>,
+    /// The [`Delay`] of the effect, if it has one.
+    pub delay: Option<TimerDefinition>,
+    /// Tags used to categorize the effect.
+    pub tags: Vec<String>,
+    /// Reflected components that are inserted onto the effect, which will usually include a [`StatusEffect`](crate::StatusEffect).
+    pub components: Vec<Box<dyn PartialReflect>>,
+}
+
+impl EffectDefinition {
+    /// Parses a definition from a RON string.
+    pub fn from_ron(
+        ron: &str,
+        type_registry: &TypeRegistry,
+    ) -> Result<Self, ron::de::SpannedError> {
+        let mut deserializer = ron::de::Deserializer::from_str(ron)?;
+        EffectDefinitionDeserializer { type_registry }
+            .deserialize(&mut deserializer)
+            .map_err(|error| deserializer.span_error(error))
+    }
+
+    fn lifetime(&self) -> Result<Option<Lifetime>, EffectDefinitionError> {
+        self.lifetime
+            .as_ref()
+            .map(TimerDefinition::to_timer)
+            .transpose()
+    }
+
+    fn delay(&self) -> Result<Option<Delay>, EffectDefinitionError> {
+        self.delay
+            .as_ref()
+            .map(TimerDefinition::to_timer)
+            .transpose()
+    }
+}
+
+/// Describes a [`Lifetime`] or [`Delay`] timer in an [`EffectDefinition`].
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct TimerDefinition {
+    /// The duration of the timer, in seconds. Must be finite and not negative.
+    #[serde(deserialize_with = "deserialize_seconds")]
+    pub seconds: f32,
+    /// The merge mode of the timer. If not set, the timer's default is used.
+    #[serde(default)]
+    pub mode: Option<TimerMergeMode>,
+}
+
+impl TimerDefinition {
+    /// Returns the duration of the timer.
+    ///
+    /// # Errors
+    /// Returns [`EffectDefinitionError::InvalidDuration`] if the number of seconds is negative or not finite.
+    pub fn duration(&self) -> Result<Duration, EffectDefinitionError> {
+        Duration::try_from_secs_f32(self.seconds)
+            .map_err(|_| EffectDefinitionError::InvalidDuration(self.seconds))
+    }
+
+    fn to_timer<T: EffectTimer<Duration = Duration>>(&self) -> Result<T, EffectDefinitionError> {
+        let timer = T::new(self.duration()?);
+        Ok(match self.mode {
+            Some(mode) => timer.with_mode(mode),
+            None => timer,
+        })
+    }
+}
+
+fn deserialize_seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    let seconds = f32::deserialize(deserializer)?;
+
+    if Duration::try_from_secs_f32(seconds).is_err() {
+        return Err(D::Error::custom(format!(
+            "`{seconds}` is not a valid duration in seconds"
+        )));
+    }
+
+    Ok(seconds)
+}
+
+/// The tags of an effect, which are used to categorize it.
+#[derive(Component, Reflect, Serialize, Deserialize, Eq, PartialEq, Debug, Default, Clone)]
+#[reflect(Component, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct EffectTags(pub Vec<String>);
+
+impl EffectTags {
+    /// Returns true if the effect has the tag.
+    pub fn contains(&self, tag: &str) -> bool {
+        self.0.iter().any(|t| t == tag)
+    }
+}
+
+/// Stores the definition that an effect was spawned from.
+#[derive(Component, Reflect, Eq, PartialEq, Debug, Clone)]
+#[reflect(Component, PartialEq, Debug, Clone)]
+pub struct EffectDefinitionHandle(pub Handle<EffectDefinition>);
+
+/// An error that can occur when spawning an effect from an [`EffectDefinition`].
+#[derive(Error, Debug)]
+pub enum EffectDefinitionError {
+    /// The definition hasn't been loaded.
+    #[error("the effect definition is not loaded")]
+    NotLoaded,
+    /// The world doesn't contain an [`AppTypeRegistry`].
+    #[error("the world doesn't contain an `AppTypeRegistry`")]
+    MissingTypeRegistry,
+    /// One of the definition's components isn't a registered component.
+    #[error("`{0}` is not registered with `#[reflect(Component)]`")]
+    NotComponent(String),
+    /// One of the definition's timers has a negative or non-finite duration.
+    #[error("`{0}` is not a valid duration in seconds")]
+    InvalidDuration(f32),
+    /// The effect was spawned, but the target rejected it.
+    #[error(transparent)]
+    Rejected(#[from] StatusEffectError),
+}
+
+/// Spawns an effect from a definition, along with a bundle,
+/// and then applies it to the target the same way as [`apply_effect`](crate::apply_effect).
+///
+/// The reflected components are inserted after the rest of the effect, so the effect hook can see them.
+/// The bundle shouldn't contain [`Effecting`](crate::Effecting), since it is added once the effect has been accepted.
+pub fn spawn_effect_definition(
+    world: &mut World,
+    target: Entity,
+    definition: &Handle<EffectDefinition>,
+    bundle: impl Bundle,
+) -> Result<Entity, EffectDefinitionError> {
+    check_target(world, target)?;
+
+    let entity = world
+        .spawn((bundle, EffectDefinitionHandle(definition.clone())))
+        .id();
+
+    if let Err(error) = insert_definition(world, definition, entity) {
+        world.despawn(entity);
+        return Err(error);
+    }
+
+    accept_effect(world, entity, target)?;
+    Ok(entity)
+}
+
+/// Inserts the components of the definition onto the entity.
+fn insert_definition(
+    world: &mut World,
+    definition: &Handle<EffectDefinition>,
+    entity: Entity,
+) -> Result<(), EffectDefinitionError> {
+    let type_registry = world
+        .get_resource::<AppTypeRegistry>()
+        .ok_or(EffectDefinitionError::MissingTypeRegistry)?
+        .clone();
+    let type_registry = type_registry.read();
+
+    if !world.contains_resource::<Assets<EffectDefinition>>() {
+        return Err(EffectDefinitionError::NotLoaded);
+    }
+
+    world.resource_scope(|world, assets: Mut<Assets<EffectDefinition>>| {
+        let definition = assets
+            .get(definition)
+            .ok_or(EffectDefinitionError::NotLoaded)?;
+        let lifetime = definition.lifetime()?;
+        let delay = definition.delay()?;
+
+        let mut entity = world.entity_mut(entity);
+        entity.insert((definition.mode, EffectTags(definition.tags.clone())));
+
+        if let Some(lifetime) = lifetime {
+            entity.insert(lifetime);
+        }
+
+        if let Some(delay) = delay {
+            entity.insert(delay);
+        }
+
+        for component in &definition.components {
+            let type_path = component
+                .get_represented_type_info()
+                .map(|info| info.type_path())
+                .unwrap_or_else(|| component.reflect_type_path());
+
+            let reflect_component = type_registry
+                .get_with_type_path(type_path)
+                .and_then(|registration| registration.data::<ReflectComponent>())
+                .ok_or_else(|| EffectDefinitionError::NotComponent(type_path.to_string()))?;
+
+            reflect_component.insert(&mut entity, component.as_ref(), &type_registry);
+        }
+
+        Ok(())
+    })
+}
+
+/// A [`Command`] that spawns an effect from a definition using [`spawn_effect_definition`], and applies it to the target.
+pub struct SpawnEffectDefinition {
+    /// The definition of the effect.
+    pub definition: Handle<EffectDefinition>,
+    /// The entity that the effect will be effecting.
+    pub target: Entity,
+}
+
+impl Command<Result> for SpawnEffectDefinition {
+    fn apply(self, world: &mut World) -> Result {
+        spawn_effect_definition(world, self.target, &self.definition, ())?;
+        Ok(())
+    }
+}
+
+/// Updates effects that were spawned from a definition, when the definition is modified.
+fn update_active_definitions(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<EffectDefinition>>,
+    effects: Query<(Entity, &EffectDefinitionHandle)>,
+) {
+    for event in events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+
+        for (entity, handle) in &effects {
+            if handle.0.id() == *id {
+                let handle = handle.0.clone();
+                commands.queue(move |world: &mut World| update_definition(world, entity, &handle));
+            }
+        }
+    }
+}
+
+/// Re-inserts the definition's components onto the entity.
+/// Timers keep their elapsed time, but use the new duration, and are removed if they were removed from the definition.
+fn update_definition(world: &mut World, entity: Entity, definition: &Handle<EffectDefinition>) {
+    if world.get_entity(entity).is_err() {
+        return;
+    }
+
+    let Some((has_lifetime, has_delay)) = world
+        .get_resource::<Assets<EffectDefinition>>()
+        .and_then(|assets| assets.get(definition))
+        .map(|definition| (definition.lifetime.is_some(), definition.delay.is_some()))
+    else {
+        return;
+    };
+
+    let old_lifetime = world.get::<Lifetime>(entity).cloned();
+    let old_delay = world.get::<Delay>(entity).cloned();
+
+    if let Err(error) = insert_definition(world, definition, entity) {
+        tracing::warn!("Failed to update effect from definition: {error}");
+        return;
+    }
+
+    if !has_lifetime {
+        world.entity_mut(entity).remove::<Lifetime>();
+    }
+
+    if !has_delay {
+        world.entity_mut(entity).remove::<Delay>();
+    }
+
+    if let Some(old) = old_lifetime
+        && let Some(mut lifetime) = world.get_mut::<Lifetime>(entity)
+    {
+        keep_elapsed(&mut lifetime.timer, &old.timer);
+    }
+
+    if let Some(old) = old_delay
+        && let Some(mut delay) = world.get_mut::<Delay>(entity)
+    {
+        keep_elapsed(&mut delay.timer, &old.timer);
+    }
+}
+
+fn keep_elapsed(timer: &mut bevy_time::Timer, old: &bevy_time::Timer) {
+    timer.set_elapsed(old.elapsed().min(timer.duration()));
+}
+
+/// Loads [`EffectDefinition`] assets from `.effect.ron` files.
+pub struct EffectDefinitionLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for EffectDefinitionLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            type_registry: world.resource::<AppTypeRegistry>().0.clone(),
+        }
+    }
+}
+
+/// An error that can occur when loading an [`EffectDefinition`].
+#[derive(Error, Debug)]
+pub enum EffectDefinitionLoaderError {
+    /// The file couldn't be read.
+    #[error("failed to read the effect definition: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file isn't a valid definition.
+    #[error("failed to parse the effect definition: {0}")]
+    Ron(#[from] ron::de::SpannedError),
+    /// The file isn't valid UTF-8.
+    #[error("the effect definition is not valid UTF-8: {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+}
+
+impl AssetLoader for EffectDefinitionLoader {
+    type Asset = EffectDefinition;
+    type Settings = ();
+    type Error = EffectDefinitionLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        Ok(EffectDefinition::from_ron(
+            std::str::from_utf8(&bytes)?,
+            &self.type_registry.read(),
+        )?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["effect.ron"]
+    }
+}
+
+/// Deserializes an [`EffectDefinition`], using the type registry to deserialize its components.
+pub struct EffectDefinitionDeserializer<'a> {
+    /// The type registry used to deserialize components.
+    pub type_registry: &'a TypeRegistry,
+}
+
+const FIELDS: &[&str] = &["name", "mode", "lifetime", "delay", "tags", "components"];
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum Field {
+    Name,
+    Mode,
+    Lifetime,
+    Delay,
+    Tags,
+    Components,
+}
+
+impl<'de> DeserializeSeed<'de> for EffectDefinitionDeserializer<'_> {
+    type Value = EffectDefinition;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("EffectDefinition", FIELDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for EffectDefinitionDeserializer<'_> {
+    type Value = EffectDefinition;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an effect definition")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut definition = EffectDefinition {
+            name: String::new(),
+            mode: EffectMode::default(),
+            lifetime: None,
+            delay: None,
+            tags: Vec::new(),
+            components: Vec::new(),
+        };
+
+        while let Some(field) = map.next_key::<Field>()? {
+            match field {
+                Field::Name => definition.name = map.next_value()?,
+                Field::Mode => definition.mode = map.next_value()?,
+                Field::Lifetime => definition.lifetime = map.next_value()?,
+                Field::Delay => definition.delay = map.next_value()?,
+                Field::Tags => definition.tags = map.next_value()?,
+                Field::Components => {
+                    definition.components = map.next_value_seed(ComponentsDeserializer {
+                        type_registry: self.type_registry,
+                        skip_unknown: false,
+                    })?
+                }
+            }
+        }
+
+        Ok(definition)
+    }
+}
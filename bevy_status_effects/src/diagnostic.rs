@@ -0,0 +1,160 @@
+use crate::dynamic::{DynamicEffectRegistry, DynamicEffects};
+use crate::id::{EffectId, EffectRegistry};
+use crate::relation::Effecting;
+use bevy_app::{App, Last, Plugin};
+use bevy_diagnostic::{
+    Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, RegisterDiagnostic,
+};
+use bevy_ecs::archetype::Archetypes;
+use bevy_ecs::prelude::*;
+use std::collections::HashMap;
+
+/// Adds diagnostics for the number of active status effects, and how often they are applied and removed.
+///
+/// A per-type count is also recorded for every registered effect type and [`DynamicEffect`] kind,
+/// using the path returned by [`type_count_path`](Self::type_count_path).
+#[derive(Default)]
+pub struct StatusEffectDiagnosticsPlugin;
+
+impl Plugin for StatusEffectDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::ACTIVE))
+            .register_diagnostic(Diagnostic::new(Self::APPLIED))
+            .register_diagnostic(Diagnostic::new(Self::REMOVED))
+            .init_resource::<EffectChangeCounts>()
+            .add_observer(count_applied)
+            .add_observer(count_removed)
+            .add_systems(
+                Last,
+                (register_type_diagnostics, Self::diagnostic_system).chain(),
+            );
+    }
+}
+
+impl StatusEffectDiagnosticsPlugin {
+    /// The number of entities that are [effecting](Effecting) another entity.
+    pub const ACTIVE: DiagnosticPath = DiagnosticPath::const_new("status_effects/active");
+    /// The number of effects that were applied this frame.
+    pub const APPLIED: DiagnosticPath = DiagnosticPath::const_new("status_effects/applied");
+    /// The number of effects that were removed this frame.
+    pub const REMOVED: DiagnosticPath = DiagnosticPath::const_new("status_effects/removed");
+
+    /// Returns the path of the diagnostic that counts the effects of a single type.
+    ///
+    /// The name is the effect's [`EffectId`], or its type name if it doesn't have one.
+    /// Any `/` in the name is replaced with `_`, so ids from mods or data files can't create an invalid path.
+    pub fn type_count_path(name: &str) -> DiagnosticPath {
+        let name = match name.replace('/', "_") {
+            name if name.is_empty() => String::from("_"),
+            name => name,
+        };
+
+        DiagnosticPath::new(format!("status_effects/count/{name}"))
+    }
+
+    /// Records the values of all status effect diagnostics.
+    pub fn diagnostic_system(
+        mut diagnostics: Diagnostics,
+        mut counts: ResMut<EffectChangeCounts>,
+        effects: Query<(), With<Effecting>>,
+        dynamic_effects: Query<&DynamicEffects>,
+        archetypes: &Archetypes,
+        registry: Option<Res<EffectRegistry>>,
+        dynamic_registry: Option<Res<DynamicEffectRegistry>>,
+    ) {
+        diagnostics.add_measurement(&Self::ACTIVE, || effects.iter().count() as f64);
+        diagnostics.add_measurement(&Self::APPLIED, || counts.applied as f64);
+        diagnostics.add_measurement(&Self::REMOVED, || counts.removed as f64);
+        *counts = EffectChangeCounts::default();
+
+        if let Some(registry) = registry {
+            for registration in registry.iter() {
+                let path = Self::type_count_path(&type_count_name(
+                    registration.id.as_ref(),
+                    registration.type_name,
+                ));
+
+                diagnostics.add_measurement(&path, || {
+                    archetypes
+                        .iter()
+                        .filter(|archetype| archetype.contains(registration.component_id))
+                        .map(|archetype| archetype.len())
+                        .sum::<usize>() as f64
+                });
+            }
+        }
+
+        if let Some(dynamic_registry) = dynamic_registry {
+            let mut dynamic_counts = HashMap::<&EffectId, u32>::new();
+            for kind in dynamic_effects.iter().flat_map(DynamicEffects::kinds) {
+                *dynamic_counts.entry(kind).or_default() += 1;
+            }
+
+            for (kind, _) in dynamic_registry.iter() {
+                diagnostics.add_measurement(&Self::type_count_path(kind.as_str()), || {
+                    dynamic_counts.get(kind).copied().unwrap_or_default() as f64
+                });
+            }
+        }
+    }
+}
+
+/// The number of effects that have been applied and removed since the diagnostics were last recorded.
+#[derive(Resource, Eq, PartialEq, Debug, Default, Copy, Clone)]
+pub struct EffectChangeCounts {
+    /// The number of effects that were applied.
+    pub applied: u32,
+    /// The number of effects that were removed.
+    pub removed: u32,
+}
+
+fn count_applied(_: Trigger<OnAdd, Effecting>, mut counts: ResMut<EffectChangeCounts>) {
+    counts.applied += 1;
+}
+
+fn count_removed(_: Trigger<OnRemove, Effecting>, mut counts: ResMut<EffectChangeCounts>) {
+    counts.removed += 1;
+}
+
+fn type_count_name(id: Option<&EffectId>, type_name: &str) -> String {
+    match id {
+        Some(id) => id.to_string(),
+        None => type_name.to_string(),
+    }
+}
+
+/// Adds a count diagnostic for any effect type or dynamic kind that doesn't have one yet.
+fn register_type_diagnostics(
+    mut store: ResMut<DiagnosticsStore>,
+    registry: Option<Res<EffectRegistry>>,
+    dynamic_registry: Option<Res<DynamicEffectRegistry>>,
+) {
+    let changed = registry
+        .as_ref()
+        .is_some_and(|registry| registry.is_changed())
+        || dynamic_registry
+            .as_ref()
+            .is_some_and(|registry| registry.is_changed());
+
+    if !changed {
+        return;
+    }
+
+    let names = registry
+        .iter()
+        .flat_map(|registry| registry.iter())
+        .map(|registration| type_count_name(registration.id.as_ref(), registration.type_name))
+        .chain(
+            dynamic_registry
+                .iter()
+                .flat_map(|registry| registry.iter())
+                .map(|(kind, _)| kind.to_string()),
+        );
+
+    for name in names {
+        let path = StatusEffectDiagnosticsPlugin::type_count_path(&name);
+        if store.get(&path).is_none() {
+            store.add(Diagnostic::new(path));
+        }
+    }
+}
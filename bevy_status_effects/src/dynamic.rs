@@ -0,0 +1,430 @@
+use crate::EffectMode;
+use crate::apply::accept_effect;
+use crate::hook::{init_effect_index, refresh_effect, unindex_effect};
+use crate::id::{EffectId, EffectIdCollision, EffectRegistry};
+use crate::index::EffectKey;
+use crate::reflect::{ReflectEffectError, check_target};
+use crate::{ReflectComponent, ReflectDefault};
+use bevy_ecs::component::HookContext;
+use bevy_ecs::prelude::*;
+use bevy_ecs::world::DeferredWorld;
+use bevy_reflect::{ApplyError, FromReflect, PartialReflect, Reflect, TypePath};
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use std::any::type_name;
+use std::collections::{BTreeMap, HashMap};
+use tracing::debug_span;
+
+/// The data of a [`DynamicEffect`], as a map of field names to values.
+pub type DynamicData = BTreeMap<String, DynamicValue>;
+
+/// A single field in the data of a [`DynamicEffect`].
+///
+/// Unlike a [`DynamicStruct`](bevy_reflect::DynamicStruct), values can be reflected and serialized without knowing their type,
+/// so dynamic effects can be saved, inspected, and included in scenes like any other component.
+#[derive(Reflect, PartialEq, Debug, Clone)]
+#[reflect(no_field_bounds, PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged),
+    reflect(Serialize, Deserialize)
+)]
+pub enum DynamicValue {
+    /// A boolean.
+    Bool(bool),
+    /// An integer.
+    Int(i64),
+    /// A floating point number.
+    Float(f64),
+    /// A string.
+    String(String),
+    /// A list of values.
+    List(Vec<DynamicValue>),
+}
+
+impl DynamicValue {
+    /// Returns the value if it is a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is an integer.
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a number, converting integers to floats.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Int(value) => Some(*value as f64),
+            Self::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a list.
+    pub fn as_list(&self) -> Option<&[DynamicValue]> {
+        match self {
+            Self::List(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the kind of value, such as `Int`.
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Self::Bool(_) => "Bool",
+            Self::Int(_) => "Int",
+            Self::Float(_) => "Float",
+            Self::String(_) => "String",
+            Self::List(_) => "List",
+        }
+    }
+
+    /// Converts the value into the same kind as the other value, if possible.
+    /// Integers can be converted to floats, but not the other way around.
+    fn coerce_to(self, other: &DynamicValue) -> Option<Self> {
+        match (self, other) {
+            (Self::Int(value), Self::Float(_)) => Some(Self::Float(value as f64)),
+            (value, other) if value.kind_name() == other.kind_name() => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for DynamicValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i32> for DynamicValue {
+    fn from(value: i32) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<i64> for DynamicValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u32> for DynamicValue {
+    fn from(value: u32) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<f32> for DynamicValue {
+    fn from(value: f32) -> Self {
+        Self::Float(value.into())
+    }
+}
+
+impl From<f64> for DynamicValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&str> for DynamicValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for DynamicValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<Vec<DynamicValue>> for DynamicValue {
+    fn from(value: Vec<DynamicValue>) -> Self {
+        Self::List(value)
+    }
+}
+
+/// A status effect whose type is defined at runtime, such as by a mod, instead of by a Rust struct.
+///
+/// Dynamic effects of the same [kind](DynamicEffect::kind) are treated the same way as
+/// two compiled [`StatusEffect`](crate::StatusEffect)s of the same type,
+/// so they are replaced and have their timers merged according to their [`EffectMode`].
+/// They are stored on the effect entity in [`DynamicEffects`].
+#[derive(Reflect, PartialEq, Debug, Clone)]
+#[reflect(PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct DynamicEffect {
+    kind: EffectId,
+    /// The data of the effect.
+    pub data: DynamicData,
+}
+
+impl DynamicEffect {
+    /// Creates a new dynamic effect of the given kind.
+    pub fn new(kind: impl Into<EffectId>, data: DynamicData) -> Self {
+        Self {
+            kind: kind.into(),
+            data,
+        }
+    }
+
+    /// Returns the kind of the effect, which is used instead of its type to find matching effects.
+    pub fn kind(&self) -> &EffectId {
+        &self.kind
+    }
+
+    /// Returns the value of a field in the effect's data.
+    pub fn get(&self, field: &str) -> Option<&DynamicValue> {
+        self.data.get(field)
+    }
+}
+
+/// The [`DynamicEffect`]s on an entity, which may contain multiple kinds at once,
+/// the same way an entity can have multiple compiled effect components.
+///
+/// The component is immutable, so that its kinds are re-evaluated by the effect hooks whenever they change.
+/// To add, remove, or modify a dynamic effect, insert a modified copy of the component.
+#[derive(Component, Reflect, PartialEq, Debug, Default, Clone)]
+#[component(
+    immutable,
+    on_insert = dynamic_effects_insert_hook,
+    on_replace = dynamic_effects_replace_hook
+)]
+#[reflect(Component, PartialEq, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct DynamicEffects(Vec<DynamicEffect>);
+
+impl DynamicEffects {
+    /// A builder that adds the effect, replacing any existing effect of the same kind.
+    pub fn with(mut self, effect: DynamicEffect) -> Self {
+        self.insert(effect);
+        self
+    }
+
+    /// Adds the effect, replacing any existing effect of the same kind.
+    pub fn insert(&mut self, effect: DynamicEffect) {
+        match self
+            .0
+            .iter_mut()
+            .find(|existing| existing.kind == effect.kind)
+        {
+            Some(existing) => *existing = effect,
+            None => self.0.push(effect),
+        }
+    }
+
+    /// Removes the effect of the given kind, returning it if it existed.
+    pub fn remove(&mut self, kind: &EffectId) -> Option<DynamicEffect> {
+        let index = self.0.iter().position(|effect| effect.kind == *kind)?;
+        Some(self.0.remove(index))
+    }
+
+    /// Returns the effect of the given kind.
+    pub fn get(&self, kind: &EffectId) -> Option<&DynamicEffect> {
+        self.0.iter().find(|effect| effect.kind == *kind)
+    }
+
+    /// Returns true if there is an effect of the given kind.
+    pub fn contains(&self, kind: &EffectId) -> bool {
+        self.get(kind).is_some()
+    }
+
+    /// Returns an iterator over the effects.
+    pub fn iter(&self) -> impl Iterator<Item = &DynamicEffect> {
+        self.0.iter()
+    }
+
+    /// Returns an iterator over the kinds of the effects.
+    pub fn kinds(&self) -> impl Iterator<Item = &EffectId> {
+        self.0.iter().map(DynamicEffect::kind)
+    }
+
+    /// Returns the number of effects.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if there are no effects.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+fn dynamic_kinds(world: &DeferredWorld, entity: Entity) -> Vec<EffectId> {
+    world
+        .get::<DynamicEffects>(entity)
+        .map(|effects| effects.kinds().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn dynamic_effects_insert_hook(mut world: DeferredWorld, context: HookContext) {
+    for kind in dynamic_kinds(&world, context.entity) {
+        let _span = debug_span!("dynamic_effect_hook", effect = kind.as_str()).entered();
+
+        refresh_effect(&mut world, context.entity, EffectKey::Id(kind));
+    }
+}
+
+fn dynamic_effects_replace_hook(mut world: DeferredWorld, context: HookContext) {
+    for kind in dynamic_kinds(&world, context.entity) {
+        unindex_effect(&mut world, context.entity, &EffectKey::Id(kind));
+    }
+}
+
+/// Describes a kind of [`DynamicEffect`].
+#[derive(Debug, Default)]
+pub struct DynamicEffectInfo {
+    /// The mode that the effect is spawned with, unless the bundle contains a different one.
+    pub mode: EffectMode,
+    /// The default data of the effect, which spawned data is applied on top of.
+    pub defaults: DynamicData,
+    /// The current version of the effect's data, used to [migrate](crate::EffectMigrations) it from old saves.
+    pub version: u32,
+}
+
+/// Stores all kinds of [`DynamicEffect`] that have been registered using [`register_dynamic_effect`].
+#[derive(Resource, Debug, Default)]
+pub struct DynamicEffectRegistry {
+    kinds: HashMap<EffectId, DynamicEffectInfo>,
+}
+
+impl DynamicEffectRegistry {
+    /// Returns information about the kind of dynamic effect.
+    pub fn get(&self, kind: &EffectId) -> Option<&DynamicEffectInfo> {
+        self.kinds.get(kind)
+    }
+
+    /// Returns an iterator over all registered kinds of dynamic effect.
+    pub fn iter(&self) -> impl Iterator<Item = (&EffectId, &DynamicEffectInfo)> {
+        self.kinds.iter()
+    }
+}
+
+/// Registers a new kind of [`DynamicEffect`].
+///
+/// Returns an error if the id is already used by a compiled effect or another dynamic effect.
+pub fn register_dynamic_effect(
+    world: &mut World,
+    kind: impl Into<EffectId>,
+    info: DynamicEffectInfo,
+) -> Result<(), EffectIdCollision> {
+    let kind = kind.into();
+
+    let existing = world
+        .get_resource::<EffectRegistry>()
+        .and_then(|registry| registry.get(&kind))
+        .map(|registration| registration.type_name);
+    let existing = existing.or_else(|| {
+        world
+            .get_resource::<DynamicEffectRegistry>()
+            .and_then(|registry| registry.get(&kind))
+            .map(|_| type_name::<DynamicEffect>())
+    });
+
+    if let Some(existing) = existing {
+        return Err(EffectIdCollision {
+            id: kind,
+            existing,
+            new: type_name::<DynamicEffect>(),
+        });
+    }
+
+    world
+        .get_resource_or_init::<DynamicEffectRegistry>()
+        .kinds
+        .insert(kind, info);
+    init_effect_index(world);
+
+    Ok(())
+}
+
+/// Spawns a [`DynamicEffect`] of the given kind, along with a bundle,
+/// and then applies it to the target the same way as [`apply_effect`](crate::apply_effect).
+///
+/// The effect's data starts as the kind's [defaults](DynamicEffectInfo::defaults), and then `data` is applied on top of it.
+/// The data should be a reflected [`DynamicData`], and each field must be the same kind of [`DynamicValue`] as its default.
+/// The bundle shouldn't contain [`Effecting`](crate::Effecting), since it is added once the effect has been accepted.
+pub fn spawn_dynamic_effect(
+    world: &mut World,
+    target: Entity,
+    kind: &EffectId,
+    data: Option<&dyn PartialReflect>,
+    bundle: impl Bundle,
+) -> Result<Entity, ReflectEffectError> {
+    check_target(world, target)?;
+
+    let effect = spawn_dynamic_data(world, kind, data, bundle)?;
+    accept_effect(world, effect, target)?;
+    Ok(effect)
+}
+
+/// Spawns a [`DynamicEffect`] of the given kind, along with a bundle, without applying it to a target.
+pub(crate) fn spawn_dynamic_data(
+    world: &mut World,
+    kind: &EffectId,
+    data: Option<&dyn PartialReflect>,
+    bundle: impl Bundle,
+) -> Result<Entity, ReflectEffectError> {
+    let info = world
+        .get_resource::<DynamicEffectRegistry>()
+        .and_then(|registry| registry.get(kind))
+        .ok_or_else(|| ReflectEffectError::UnknownId(kind.clone()))?;
+
+    let mode = info.mode;
+    let mut effect_data = info.defaults.clone();
+
+    if let Some(data) = data {
+        let data = DynamicData::from_reflect(data).ok_or_else(|| ApplyError::MismatchedTypes {
+            from_type: data.reflect_type_path().into(),
+            to_type: DynamicData::type_path().into(),
+        })?;
+
+        for (field, value) in data {
+            let value =
+                match effect_data.get(&field) {
+                    Some(default) => value.coerce_to(default).ok_or_else(|| {
+                        ReflectEffectError::DynamicField {
+                            kind: kind.clone(),
+                            field: field.clone(),
+                            expected: default.kind_name(),
+                        }
+                    })?,
+                    None => value,
+                };
+
+            effect_data.insert(field, value);
+        }
+    }
+
+    let mut entity = world.spawn(mode);
+    entity
+        .insert(bundle)
+        .insert(DynamicEffects::default().with(DynamicEffect::new(kind.clone(), effect_data)));
+
+    Ok(entity.id())
+}
@@ -0,0 +1,56 @@
+use crate::id::EffectIdCollision;
+use crate::index::EffectKey;
+use bevy_ecs::prelude::*;
+use std::borrow::Cow;
+use std::time::Duration;
+use thiserror::Error;
+
+/// An error that can occur when registering or applying a status effect.
+#[derive(Error, Eq, PartialEq, Debug, Clone)]
+pub enum StatusEffectError {
+    /// The target doesn't exist, or is the effect itself.
+    #[error("{0} isn't a valid target")]
+    InvalidTarget(Entity),
+    /// The effect entity doesn't exist, or is being [transferred](crate::transfer_effect) without [effecting](crate::Effecting) anything.
+    #[error("{0} isn't a valid effect")]
+    InvalidEffect(Entity),
+    /// The target is [immune](crate::EffectImmunity) to the effect.
+    #[error("{target} is immune to {key:?}")]
+    Immune {
+        /// The entity that the effect was applied to.
+        target: Entity,
+        /// The key of the effect that was rejected.
+        key: EffectKey,
+    },
+    /// The target already has the maximum number of [stacked](crate::EffectMode::Stack) effects.
+    #[error("{target} already has {limit} stacks of {key:?}")]
+    StackLimit {
+        /// The entity that the effect was applied to.
+        target: Entity,
+        /// The key of the effect that was rejected.
+        key: EffectKey,
+        /// The target's [`EffectStackLimit`](crate::EffectStackLimit).
+        limit: usize,
+    },
+    /// The effect is on [cooldown](crate::EffectCooldowns) for the target.
+    #[error("{key:?} is on cooldown for {target} for another {remaining:?}")]
+    Cooldown {
+        /// The entity that the effect was applied to.
+        target: Entity,
+        /// The key of the effect that was rejected.
+        key: EffectKey,
+        /// The time remaining until the effect can be applied again.
+        remaining: Duration,
+    },
+    /// An observer [cancelled](crate::ApplyingEffect::cancel) the effect before it was applied.
+    #[error("applying an effect to {target} was cancelled: {reason}")]
+    Cancelled {
+        /// The entity that the effect was applied to.
+        target: Entity,
+        /// The reason given by the observer.
+        reason: Cow<'static, str>,
+    },
+    /// Two different status effect types use the same id.
+    #[error(transparent)]
+    IdCollision(#[from] EffectIdCollision),
+}
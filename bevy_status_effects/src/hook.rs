@@ -1,22 +1,169 @@
-use crate::relation::{EffectedBy, Effecting};
+use crate::dynamic::DynamicEffects;
+use crate::error::StatusEffectError;
+use crate::id::{EffectRegistration, EffectRegistry};
+use crate::index::{EffectIndex, EffectKey};
+use crate::intensity::refresh_intensity;
+use crate::relation::Effecting;
+use crate::sequence::{EffectSequenceCounter, assign_sequence};
+use crate::snapshot::merge_snapshots;
+use crate::step::{StepClock, StepDelay, StepLifetime};
+use crate::tick::Ticks;
 use crate::timer::{Delay, EffectTimer, Lifetime};
+use crate::turn::Turns;
 use crate::{EffectMode, StatusEffect};
-use bevy_ecs::component::HookContext;
-use bevy_ecs::prelude::{Component, RelationshipTarget, World};
-use bevy_ecs::world::DeferredWorld;
+use bevy_ecs::component::{ComponentId, HookContext, Mutable};
+use bevy_ecs::prelude::{Component, Entity, OnInsert, OnReplace, Trigger, World};
+use bevy_ecs::world::{DeferredWorld, EntityRef};
+use std::any::type_name;
+use tracing::debug_span;
 
-/// A system that registers the effect hook for a given type.
+/// A system that registers the effect hooks for a given type, and adds it to the [`EffectRegistry`].
+///
+/// If the effect's [`EffectId`](crate::EffectId) is already used by a different effect,
+/// a command that fails with [`StatusEffectError::IdCollision`] is queued, so the error is reported by Bevy's error handler.
+/// Use [`try_init_effect_hook`] to handle the error instead.
 pub fn init_effect_hook<T: Component + StatusEffect>(world: &mut World) {
+    if let Err(error) = try_init_effect_hook::<T>(world) {
+        world
+            .commands()
+            .queue(move |_: &mut World| Err::<(), _>(error));
+    }
+}
+
+/// Registers the effect hooks for a given type, and adds it to the [`EffectRegistry`].
+///
+/// # Errors
+/// Returns [`StatusEffectError::IdCollision`] if the effect's [`EffectId`](crate::EffectId) is already used by a different effect.
+pub fn try_init_effect_hook<T: Component + StatusEffect>(
+    world: &mut World,
+) -> Result<(), StatusEffectError> {
+    let component_id = world.register_component::<T>();
+
+    world
+        .get_resource_or_init::<EffectRegistry>()
+        .register::<T>(component_id)?;
+
+    init_effect_index(world);
+
     world
         .register_component_hooks::<T>()
-        .on_add(effect_refresh_hook::<T>);
+        .on_add(effect_refresh_hook::<T>)
+        .on_remove(effect_remove_hook::<T>);
+
+    Ok(())
 }
 
 fn effect_refresh_hook<T: Component + StatusEffect>(
     mut world: DeferredWorld,
     context: HookContext,
 ) {
-    let Some(mode) = world.get::<EffectMode>(context.entity).copied() else {
+    let _span = debug_span!("effect_refresh_hook", effect = type_name::<T>()).entered();
+
+    refresh_effect(&mut world, context.entity, EffectKey::of::<T>());
+}
+
+fn effect_remove_hook<T: Component + StatusEffect>(mut world: DeferredWorld, context: HookContext) {
+    unindex_effect(&mut world, context.entity, &EffectKey::of::<T>());
+}
+
+/// Initializes the [`EffectIndex`], along with the observers that update it when [`Effecting`] is inserted or replaced.
+pub(crate) fn init_effect_index(world: &mut World) {
+    if world.contains_resource::<EffectIndex>() {
+        return;
+    }
+
+    world.init_resource::<EffectIndex>();
+    world.init_resource::<EffectSequenceCounter>();
+    world.add_observer(effecting_insert_observer);
+    world.add_observer(effecting_replace_observer);
+}
+
+/// Refreshes the effect when it is given a target, which may happen after the effect component was added.
+fn effecting_insert_observer(trigger: Trigger<OnInsert, Effecting>, mut world: DeferredWorld) {
+    refresh_all_effects(&mut world, trigger.target());
+}
+
+fn effecting_replace_observer(trigger: Trigger<OnReplace, Effecting>, mut world: DeferredWorld) {
+    unindex_all_effects(&mut world, trigger.target());
+}
+
+/// Refreshes the effect when its mode is inserted, which may happen after the effect component was added.
+pub(crate) fn effect_mode_insert_hook(mut world: DeferredWorld, context: HookContext) {
+    refresh_all_effects(&mut world, context.entity);
+}
+
+pub(crate) fn effect_mode_replace_hook(mut world: DeferredWorld, context: HookContext) {
+    unindex_all_effects(&mut world, context.entity);
+}
+
+/// Returns the [`EffectKey`] of every effect component on the entity.
+pub(crate) fn effect_keys(registry: Option<&EffectRegistry>, entity: EntityRef) -> Vec<EffectKey> {
+    let mut keys = component_effect_keys(registry, |id| entity.contains_id(id));
+
+    if let Some(dynamic) = entity.get::<DynamicEffects>() {
+        keys.extend(dynamic.kinds().cloned().map(EffectKey::Id));
+    }
+
+    keys
+}
+
+/// Returns the [`EffectKey`] of every registered effect type whose component is contained.
+///
+/// Unlike [`effect_keys`], this doesn't include the kinds of any [`DynamicEffects`], since they aren't separate components.
+pub(crate) fn component_effect_keys(
+    registry: Option<&EffectRegistry>,
+    contains: impl Fn(ComponentId) -> bool,
+) -> Vec<EffectKey> {
+    registry
+        .into_iter()
+        .flat_map(|registry| registry.iter())
+        .filter(|registration| contains(registration.component_id))
+        .map(EffectRegistration::key)
+        .collect()
+}
+
+fn world_effect_keys(world: &DeferredWorld, effect: Entity) -> Vec<EffectKey> {
+    match world.get_entity(effect) {
+        Ok(effect) => effect_keys(world.get_resource::<EffectRegistry>(), effect),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Returns true if the entity's effects can be indexed, which requires them to not be [stacked](EffectMode::Stack).
+fn is_indexable(world: &DeferredWorld, entity: Entity) -> bool {
+    world
+        .get::<EffectMode>(entity)
+        .is_some_and(|mode| *mode != EffectMode::Stack)
+}
+
+/// Calls [`refresh_effect`] for every effect component on the entity.
+fn refresh_all_effects(world: &mut DeferredWorld, effect: Entity) {
+    if !is_indexable(world, effect) {
+        return;
+    }
+
+    for key in world_effect_keys(world, effect) {
+        refresh_effect(world, effect, key);
+    }
+}
+
+/// Calls [`unindex_effect`] for every effect component on the entity.
+fn unindex_all_effects(world: &mut DeferredWorld, effect: Entity) {
+    if !is_indexable(world, effect) {
+        return;
+    }
+
+    for key in world_effect_keys(world, effect) {
+        unindex_effect(world, effect, &key);
+    }
+}
+
+/// Replaces any existing effect on the effect's target, if they have the same [`EffectMode`] and [`EffectKey`].
+/// The effect with the higher [`EffectSequence`](crate::EffectSequence) is kept, and added to the [`EffectIndex`].
+///
+/// Does nothing if the effect is already indexed, so it is safe to call once per inserted component.
+pub(crate) fn refresh_effect(world: &mut DeferredWorld, effect: Entity, key: EffectKey) {
+    let Some(mode) = world.get::<EffectMode>(effect).copied() else {
         return;
     };
 
@@ -24,48 +171,81 @@ fn effect_refresh_hook<T: Component + StatusEffect>(
         return;
     }
 
-    let Some(target) = world.get::<Effecting>(context.entity) else {
+    let Some(target) = world.get::<Effecting>(effect).map(|effecting| effecting.0) else {
         return;
     };
 
-    let effected_by = match world.get::<EffectedBy>(target.0) {
-        Some(e) => e.collection().clone(),
-        None => return,
+    let Some(index) = world.get_resource::<EffectIndex>() else {
+        return;
     };
 
-    let old = effected_by.iter().find_map(|entity| {
-        // `EffectedBy` not updated until later.
-        assert_ne!(*entity, context.entity);
+    let indexed = index.get(target, &key);
 
-        let Some(other_mode) = world.get::<EffectMode>(*entity) else {
-            return None;
-        };
+    if indexed.contains(&effect) {
+        return;
+    }
 
-        if mode != *other_mode {
-            return None;
-        }
+    let old = indexed
+        .iter()
+        .copied()
+        .find(|entity| *entity != effect && world.get::<EffectMode>(*entity) == Some(&mode));
 
+    let Some(old) = old else {
         world
-            .get::<T>(*entity)
-            .and_then(|effect| Some((*entity, effect)))
-    });
-
-    if let Some((old_entity, _old_effect)) = old {
-        match mode {
-            EffectMode::Stack => return,
-            EffectMode::Replace => world.commands().entity(old_entity).despawn(),
-        }
-
-        if let Some(old_lifetime) = world.get::<Lifetime>(old_entity).cloned() {
-            if let Some(mut lifetime) = world.get_mut::<Lifetime>(context.entity) {
-                lifetime.merge(&old_lifetime)
-            }
-        }
-
-        if let Some(old_delay) = world.get::<Delay>(old_entity).cloned() {
-            if let Some(mut delay) = world.get_mut::<Delay>(context.entity) {
-                delay.merge(&old_delay)
-            }
-        }
+            .resource_mut::<EffectIndex>()
+            .insert(target, key, effect);
+        return;
+    };
+
+    // The effect that was applied last survives, even if it was spawned first.
+    let (new, old) = if assign_sequence(world, effect) >= assign_sequence(world, old) {
+        world
+            .resource_mut::<EffectIndex>()
+            .insert(target, key, effect);
+        (effect, old)
+    } else {
+        (old, effect)
+    };
+
+    match mode {
+        EffectMode::Stack => return,
+        EffectMode::Replace => world.commands().entity(old).try_despawn(),
+    };
+
+    merge_timer::<Lifetime>(world, old, new);
+    merge_timer::<Delay>(world, old, new);
+    merge_step_timers::<Turns>(world, old, new);
+    merge_step_timers::<Ticks>(world, old, new);
+    merge_snapshots(world, old, new);
+    refresh_intensity(world, new);
+}
+
+/// Removes the effect from the [`EffectIndex`].
+pub(crate) fn unindex_effect(world: &mut DeferredWorld, effect: Entity, key: &EffectKey) {
+    let Some(target) = world.get::<Effecting>(effect).map(|effecting| effecting.0) else {
+        return;
+    };
+
+    if let Some(mut index) = world.get_resource_mut::<EffectIndex>() {
+        index.remove(target, key, effect);
+    }
+}
+
+/// Merges the old entity's [`StepLifetime`] and [`StepDelay`] timers for the clock into the new entity's.
+fn merge_step_timers<C: StepClock>(world: &mut DeferredWorld, old: Entity, new: Entity) {
+    merge_timer::<StepLifetime<C>>(world, old, new);
+    merge_timer::<StepDelay<C>>(world, old, new);
+}
+
+/// Merges the old entity's timer into the new entity's timer, if they both have one.
+fn merge_timer<T: EffectTimer + Component<Mutability = Mutable> + Clone>(
+    world: &mut DeferredWorld,
+    old: Entity,
+    new: Entity,
+) {
+    if let Some(old_timer) = world.get::<T>(old).cloned()
+        && let Some(mut timer) = world.get_mut::<T>(new)
+    {
+        timer.merge(&old_timer)
     }
 }
@@ -0,0 +1,207 @@
+use crate::StatusEffect;
+use crate::dynamic::DynamicEffects;
+use crate::index::EffectKey;
+use crate::reflect::{ReflectEffectError, apply_reflected_effect};
+use bevy_ecs::component::ComponentId;
+use bevy_ecs::prelude::*;
+use bevy_reflect::{PartialReflect, Reflect};
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use std::any::{TypeId, type_name};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+
+/// A stable identifier for a status effect, which doesn't change when the type is renamed or moved.
+///
+/// Set using the `#[status_effect(id = "...")]` attribute when deriving [`StatusEffect`].
+#[derive(Reflect, Eq, PartialEq, Hash, Debug, Clone)]
+#[reflect(PartialEq, Hash, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct EffectId(Cow<'static, str>);
+
+impl EffectId {
+    /// Creates a new id from a static string.
+    pub const fn new(id: &'static str) -> Self {
+        Self(Cow::Borrowed(id))
+    }
+
+    /// Returns the id as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&'static str> for EffectId {
+    fn from(id: &'static str) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<String> for EffectId {
+    fn from(id: String) -> Self {
+        Self(Cow::Owned(id))
+    }
+}
+
+impl Display for EffectId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Information about a registered status effect type.
+#[derive(Debug, Clone)]
+pub struct EffectRegistration {
+    /// The stable id of the effect, if it has one.
+    pub id: Option<EffectId>,
+    /// The current version of the effect's data.
+    pub version: u32,
+    /// The [`TypeId`] of the effect component.
+    pub type_id: TypeId,
+    /// The type name of the effect component.
+    pub type_name: &'static str,
+    /// The [`ComponentId`] of the effect component.
+    pub component_id: ComponentId,
+}
+
+impl EffectRegistration {
+    /// Returns the [`EffectKey`] of the effect, which is its id if it has one, or otherwise its type path.
+    pub fn key(&self) -> EffectKey {
+        match &self.id {
+            Some(id) => EffectKey::Id(id.clone()),
+            None => EffectKey::TypePath(Cow::Borrowed(self.type_name)),
+        }
+    }
+}
+
+/// Stores all status effect types that have been registered using [`init_effect_hook`](crate::init_effect_hook).
+#[derive(Resource, Debug, Default)]
+pub struct EffectRegistry {
+    registrations: Vec<EffectRegistration>,
+    by_id: HashMap<EffectId, usize>,
+    by_type: HashMap<TypeId, usize>,
+}
+
+impl EffectRegistry {
+    /// Registers a status effect type.
+    ///
+    /// Returns an error if the effect's [`EffectId`] is already used by a different type.
+    /// Registering the same type multiple times does nothing.
+    pub fn register<T: Component + StatusEffect>(
+        &mut self,
+        component_id: ComponentId,
+    ) -> Result<(), EffectIdCollision> {
+        let type_id = TypeId::of::<T>();
+
+        if self.by_type.contains_key(&type_id) {
+            return Ok(());
+        }
+
+        let id = T::id();
+
+        if let Some(id) = &id {
+            if let Some(existing) = self.get(id) {
+                return Err(EffectIdCollision {
+                    id: id.clone(),
+                    existing: existing.type_name,
+                    new: type_name::<T>(),
+                });
+            }
+
+            self.by_id.insert(id.clone(), self.registrations.len());
+        }
+
+        self.by_type.insert(type_id, self.registrations.len());
+        self.registrations.push(EffectRegistration {
+            id,
+            version: T::version(),
+            type_id,
+            type_name: type_name::<T>(),
+            component_id,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the registration of the effect with the given id.
+    pub fn get(&self, id: &EffectId) -> Option<&EffectRegistration> {
+        self.by_id.get(id).map(|index| &self.registrations[*index])
+    }
+
+    /// Returns the registration of the effect with the given [`TypeId`].
+    pub fn get_by_type(&self, type_id: TypeId) -> Option<&EffectRegistration> {
+        self.by_type
+            .get(&type_id)
+            .map(|index| &self.registrations[*index])
+    }
+
+    /// Returns the id of the effect type, if it has been registered and has one.
+    pub fn id_of<T: Component + StatusEffect>(&self) -> Option<&EffectId> {
+        self.get_by_type(TypeId::of::<T>())?.id.as_ref()
+    }
+
+    /// Returns an iterator over all registered effect types.
+    pub fn iter(&self) -> impl Iterator<Item = &EffectRegistration> {
+        self.registrations.iter()
+    }
+}
+
+/// Returned when two different status effect types use the same [`EffectId`].
+#[derive(Error, Eq, PartialEq, Debug, Clone)]
+#[error("effect id `{id}` is used by both `{existing}` and `{new}`")]
+pub struct EffectIdCollision {
+    /// The id that was used multiple times.
+    pub id: EffectId,
+    /// The type name of the effect that was registered first.
+    pub existing: &'static str,
+    /// The type name of the effect that caused the collision.
+    pub new: &'static str,
+}
+
+/// Returns the ids of all registered effects on an entity, including the kinds of any [`DynamicEffects`](crate::DynamicEffects).
+pub fn effect_ids(world: &World, entity: Entity) -> Vec<EffectId> {
+    let (Some(registry), Ok(entity)) = (
+        world.get_resource::<EffectRegistry>(),
+        world.get_entity(entity),
+    ) else {
+        return Vec::new();
+    };
+
+    registry
+        .iter()
+        .filter(|registration| entity.contains_id(registration.component_id))
+        .filter_map(|registration| registration.id.clone())
+        .chain(
+            entity
+                .get::<DynamicEffects>()
+                .into_iter()
+                .flat_map(|effects| effects.kinds().cloned()),
+        )
+        .collect()
+}
+
+/// Spawns an effect with the given id, along with a bundle, and then applies it to the target.
+///
+/// The effect is created and applied the same way as [`spawn_effect_by_name`](crate::spawn_effect_by_name),
+/// except it doesn't need to be registered with `#[reflect(StatusEffect)]`.
+pub fn spawn_effect_by_id(
+    world: &mut World,
+    target: Entity,
+    id: &EffectId,
+    data: Option<&dyn PartialReflect>,
+    bundle: impl Bundle,
+) -> Result<Entity, ReflectEffectError> {
+    let registration = world
+        .get_resource::<EffectRegistry>()
+        .and_then(|registry| registry.get(id))
+        .ok_or_else(|| ReflectEffectError::UnknownId(id.clone()))?;
+    let (type_id, type_name) = (registration.type_id, registration.type_name);
+
+    apply_reflected_effect(world, target, type_id, type_name, data, bundle)
+}
@@ -0,0 +1,94 @@
+use crate::StatusEffect;
+use crate::id::EffectId;
+use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use std::any::type_name;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Identifies the type of an effect. Effects with the same key can [replace](crate::EffectMode::Replace) each other.
+///
+/// Keys are stable between runs, so they can be saved, such as in an [`EffectImmunity`](crate::EffectImmunity).
+#[derive(Reflect, Eq, PartialEq, Hash, Debug, Clone)]
+#[reflect(PartialEq, Hash, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum EffectKey {
+    /// A compiled [`StatusEffect`] with a stable [`EffectId`], or a [`DynamicEffect`](crate::DynamicEffect) of the given kind.
+    Id(EffectId),
+    /// A compiled [`StatusEffect`] without an [`EffectId`], identified by its [type name](type_name).
+    TypePath(Cow<'static, str>),
+}
+
+impl EffectKey {
+    /// Returns the key of a compiled effect, which is its [`EffectId`] if it has one, or otherwise its type path.
+    pub fn of<T: StatusEffect + 'static>() -> Self {
+        match T::id() {
+            Some(id) => Self::Id(id),
+            None => Self::TypePath(Cow::Borrowed(type_name::<T>())),
+        }
+    }
+}
+
+/// Stores the effects on each target by [`EffectKey`], so that effects that should be replaced
+/// can be found without scanning the target's entire [`EffectedBy`](crate::EffectedBy).
+///
+/// Only contains effects that aren't [stacked](crate::EffectMode::Stack), since they are never replaced.
+/// Kept up to date by the effect hooks.
+#[derive(Resource, Debug, Default)]
+pub struct EffectIndex {
+    targets: HashMap<Entity, HashMap<EffectKey, Vec<Entity>>>,
+}
+
+impl EffectIndex {
+    /// Returns the indexed effects with the given key that are effecting the target, in application order.
+    pub fn get(&self, target: Entity, key: &EffectKey) -> &[Entity] {
+        self.targets
+            .get(&target)
+            .and_then(|keys| keys.get(key))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of targets that have at least one indexed effect.
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns true if no effects are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    pub(crate) fn insert(&mut self, target: Entity, key: EffectKey, effect: Entity) {
+        self.targets
+            .entry(target)
+            .or_default()
+            .entry(key)
+            .or_default()
+            .push(effect);
+    }
+
+    pub(crate) fn remove(&mut self, target: Entity, key: &EffectKey, effect: Entity) {
+        let Some(keys) = self.targets.get_mut(&target) else {
+            return;
+        };
+
+        if let Some(effects) = keys.get_mut(key) {
+            effects.retain(|e| *e != effect);
+
+            if effects.is_empty() {
+                keys.remove(key);
+            }
+        }
+
+        if keys.is_empty() {
+            self.targets.remove(&target);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use crate::EffectMode;
+use crate::dynamic::{DynamicEffect, DynamicEffects};
+use crate::id::EffectRegistry;
+use crate::relation::EffectedBy;
+use crate::tick::{TickDelay, TickLifetime};
+use crate::timer::{Delay, Lifetime};
+use crate::turn::{TurnDelay, TurnLifetime};
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::{AppTypeRegistry, ReflectComponent};
+use bevy_ecs::world::EntityRef;
+use bevy_reflect::TypeRegistry;
+use std::any::type_name;
+use std::fmt::{Display, Formatter};
+use tracing::info;
+
+/// A snapshot of every active effect in the world, which is useful for debugging.
+///
+/// Can be printed in a human-readable format using [`Display`].
+#[derive(PartialEq, Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EffectSnapshot {
+    /// Every entity that is being effected by at least one effect, sorted by entity.
+    pub targets: Vec<TargetSnapshot>,
+}
+
+/// A snapshot of an entity that is being effected, as part of an [`EffectSnapshot`].
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TargetSnapshot {
+    /// The entity that is being effected.
+    pub entity: Entity,
+    /// The effects in the target's [`EffectedBy`], in application order.
+    pub effects: Vec<EffectEntitySnapshot>,
+}
+
+/// A snapshot of a single effect entity, as part of an [`EffectSnapshot`].
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EffectEntitySnapshot {
+    /// The effect entity.
+    pub entity: Entity,
+    /// The mode of the effect, if it has one.
+    pub mode: Option<EffectMode>,
+    /// The remaining [`Lifetime`] of the effect, in seconds.
+    pub lifetime: Option<f32>,
+    /// The remaining time until the effect's [`Delay`] next finishes, in seconds.
+    pub delay: Option<f32>,
+    /// The remaining turns of the effect's [`TurnLifetime`].
+    pub turn_lifetime: Option<u32>,
+    /// The remaining turns until the effect's [`TurnDelay`] next finishes.
+    pub turn_delay: Option<u32>,
+    /// The remaining simulation ticks of the effect's [`TickLifetime`].
+    pub tick_lifetime: Option<u32>,
+    /// The remaining simulation ticks until the effect's [`TickDelay`] next finishes.
+    pub tick_delay: Option<u32>,
+    /// The registered status effect components on the entity.
+    pub components: Vec<EffectComponentSnapshot>,
+}
+
+/// A snapshot of a single status effect component, as part of an [`EffectSnapshot`].
+#[derive(Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EffectComponentSnapshot {
+    /// The type path of the component.
+    pub type_path: String,
+    /// The reflected data of the component, formatted using [`Debug`].
+    /// Not set if the component isn't registered with `#[reflect(Component)]`.
+    pub data: Option<String>,
+}
+
+impl Display for EffectSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.targets.is_empty() {
+            return writeln!(f, "No active effects");
+        }
+
+        for target in &self.targets {
+            write!(f, "{target}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for TargetSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Target {}:", self.entity)?;
+
+        for effect in &self.effects {
+            write!(f, "{effect}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for EffectEntitySnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "  Effect {}", self.entity)?;
+
+        match self.mode {
+            Some(mode) => write!(f, " ({mode:?}")?,
+            None => write!(f, " (no mode")?,
+        }
+        if let Some(lifetime) = self.lifetime {
+            write!(f, ", lifetime {lifetime:.2}s")?;
+        }
+        if let Some(delay) = self.delay {
+            write!(f, ", delay {delay:.2}s")?;
+        }
+        if let Some(turns) = self.turn_lifetime {
+            write!(f, ", lifetime {turns} turns")?;
+        }
+        if let Some(turns) = self.turn_delay {
+            write!(f, ", delay {turns} turns")?;
+        }
+        if let Some(ticks) = self.tick_lifetime {
+            write!(f, ", lifetime {ticks} ticks")?;
+        }
+        if let Some(ticks) = self.tick_delay {
+            write!(f, ", delay {ticks} ticks")?;
+        }
+        writeln!(f, ")")?;
+
+        for component in &self.components {
+            match &component.data {
+                Some(data) => writeln!(f, "    {data}")?,
+                None => writeln!(f, "    {}", component.type_path)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates an [`EffectSnapshot`] of every active effect in the world.
+pub fn snapshot_effects(world: &World) -> EffectSnapshot {
+    let type_registry = world
+        .get_resource::<AppTypeRegistry>()
+        .map(|registry| registry.read());
+
+    let mut targets: Vec<TargetSnapshot> = world
+        .iter_entities()
+        .filter_map(|entity| {
+            let effected_by = entity.get::<EffectedBy>()?;
+
+            Some(TargetSnapshot {
+                entity: entity.id(),
+                effects: effected_by
+                    .into_iter()
+                    .filter_map(|effect| world.get_entity(*effect).ok())
+                    .map(|effect| snapshot_effect(world, type_registry.as_deref(), effect))
+                    .collect(),
+            })
+        })
+        .collect();
+
+    targets.sort_by_key(|target| target.entity);
+
+    EffectSnapshot { targets }
+}
+
+fn snapshot_effect(
+    world: &World,
+    type_registry: Option<&TypeRegistry>,
+    effect: EntityRef,
+) -> EffectEntitySnapshot {
+    let mut components = Vec::new();
+
+    if let Some(registry) = world.get_resource::<EffectRegistry>() {
+        for registration in registry.iter() {
+            if !effect.contains_id(registration.component_id) {
+                continue;
+            }
+
+            let type_registration =
+                type_registry.and_then(|type_registry| type_registry.get(registration.type_id));
+
+            let data = type_registration
+                .and_then(|type_registration| type_registration.data::<ReflectComponent>())
+                .and_then(|reflect_component| reflect_component.reflect(effect))
+                .map(|data| format!("{data:?}"));
+
+            components.push(EffectComponentSnapshot {
+                type_path: type_registration
+                    .map(|type_registration| type_registration.type_info().type_path())
+                    .unwrap_or(registration.type_name)
+                    .to_string(),
+                data,
+            });
+        }
+    }
+
+    for dynamic in effect
+        .get::<DynamicEffects>()
+        .into_iter()
+        .flat_map(DynamicEffects::iter)
+    {
+        components.push(EffectComponentSnapshot {
+            type_path: type_name::<DynamicEffect>().to_string(),
+            data: Some(format!("{}: {:?}", dynamic.kind(), dynamic.data)),
+        });
+    }
+
+    EffectEntitySnapshot {
+        entity: effect.id(),
+        mode: effect.get::<EffectMode>().copied(),
+        lifetime: effect
+            .get::<Lifetime>()
+            .map(|lifetime| lifetime.timer.remaining_secs()),
+        delay: effect
+            .get::<Delay>()
+            .map(|delay| delay.timer.remaining_secs()),
+        turn_lifetime: effect
+            .get::<TurnLifetime>()
+            .map(|lifetime| lifetime.timer.remaining()),
+        turn_delay: effect
+            .get::<TurnDelay>()
+            .map(|delay| delay.timer.remaining()),
+        tick_lifetime: effect
+            .get::<TickLifetime>()
+            .map(|lifetime| lifetime.timer.remaining()),
+        tick_delay: effect
+            .get::<TickDelay>()
+            .map(|delay| delay.timer.remaining()),
+        components,
+    }
+}
+
+/// When set to true, an [`EffectSnapshot`] is logged after the effect timers are next ticked.
+/// It is then reset back to false.
+#[derive(Resource, Eq, PartialEq, Debug, Default, Copy, Clone)]
+pub struct LogEffectSnapshot(pub bool);
+
+/// Only runs when [`LogEffectSnapshot`] is set, so it doesn't read the whole world every frame.
+pub(super) fn log_effect_snapshot(world: &World, mut commands: Commands) {
+    commands.insert_resource(LogEffectSnapshot(false));
+
+    info!("Active status effects:\n{}", snapshot_effects(world));
+}
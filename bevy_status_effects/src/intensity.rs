@@ -0,0 +1,164 @@
+use crate::ReflectComponent;
+use crate::timer::Lifetime;
+use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryData;
+use bevy_ecs::world::DeferredWorld;
+use bevy_math::curve::{ConstantCurve, Curve, EaseFunction, EasingCurve, Interval, JumpAt};
+use bevy_reflect::Reflect;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use tracing::debug_span;
+
+/// Describes how an effect's [`Intensity`] changes over its [`Lifetime`].
+///
+/// Curves are sampled using the fraction of the lifetime that has elapsed, from `0.0` to `1.0`.
+///
+/// Custom curves can't be reflected, so they appear as an empty variant,
+/// and a constant intensity of `1.0` is used when one is created from reflection.
+#[derive(Reflect, Clone)]
+#[reflect(Debug, Clone)]
+pub enum IntensityCurve {
+    /// Eases from the start value to the end value.
+    Ease {
+        /// The intensity when the effect is applied.
+        start: f32,
+        /// The intensity when the effect expires.
+        end: f32,
+        /// The easing function used between the start and end.
+        function: EaseFunction,
+    },
+    /// Samples a custom curve, such as one built using [`bevy_math::curve`].
+    Custom(#[reflect(ignore, default = "default_custom_curve")] Arc<dyn Curve<f32> + Send + Sync>),
+}
+
+impl IntensityCurve {
+    /// Creates a curve that changes linearly from the start value to the end value.
+    pub fn linear(start: f32, end: f32) -> Self {
+        Self::ease(start, end, EaseFunction::Linear)
+    }
+
+    /// Creates a curve that eases from the start value to the end value.
+    pub fn ease(start: f32, end: f32, function: EaseFunction) -> Self {
+        Self::Ease {
+            start,
+            end,
+            function,
+        }
+    }
+
+    /// Creates a curve that moves from the start value to the end value in a number of equal steps.
+    pub fn steps(start: f32, end: f32, steps: usize) -> Self {
+        Self::ease(start, end, EaseFunction::Steps(steps, JumpAt::End))
+    }
+
+    /// Creates a curve from any [`Curve`].
+    pub fn custom(curve: impl Curve<f32> + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(curve))
+    }
+
+    /// Returns the intensity after the given fraction of the effect's lifetime has elapsed.
+    pub fn sample(&self, fraction: f32) -> f32 {
+        let fraction = fraction.clamp(0.0, 1.0);
+
+        match self {
+            Self::Ease {
+                start,
+                end,
+                function,
+            } => EasingCurve::new(*start, *end, *function).sample_clamped(fraction),
+            Self::Custom(curve) => curve.sample_clamped(fraction),
+        }
+    }
+}
+
+fn default_custom_curve() -> Arc<dyn Curve<f32> + Send + Sync> {
+    Arc::new(ConstantCurve::new(Interval::UNIT, 1.0))
+}
+
+impl Debug for IntensityCurve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ease {
+                start,
+                end,
+                function,
+            } => f
+                .debug_struct("Ease")
+                .field("start", start)
+                .field("end", end)
+                .field("function", function)
+                .finish(),
+            Self::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Scales how strong an effect is, based on how much of its [`Lifetime`] has elapsed.
+///
+/// Evaluated every time effect timers are ticked, and when the lifetime is merged with a replaced effect.
+/// Can be read using the [`EffectIntensity`] query helper.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Debug, Clone)]
+pub struct Intensity {
+    /// The curve that the intensity follows.
+    pub curve: IntensityCurve,
+    value: f32,
+}
+
+impl Intensity {
+    /// Creates an intensity that follows the curve.
+    pub fn new(curve: IntensityCurve) -> Self {
+        let value = curve.sample(0.0);
+        Self { curve, value }
+    }
+
+    /// Returns the current intensity.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Re-evaluates the intensity from the lifetime.
+    pub fn update(&mut self, lifetime: &Lifetime) {
+        self.value = self.curve.sample(lifetime.timer.fraction());
+    }
+}
+
+/// A [`QueryData`] that reads the intensity of an effect, which is `1.0` if it doesn't have an [`Intensity`].
+#[derive(QueryData)]
+pub struct EffectIntensity {
+    intensity: Option<&'static Intensity>,
+}
+
+impl EffectIntensityItem<'_> {
+    /// Returns the effect's intensity.
+    pub fn get(&self) -> f32 {
+        self.intensity.map_or(1.0, Intensity::value)
+    }
+
+    /// Scales a value by the effect's intensity.
+    pub fn scale(&self, value: f32) -> f32 {
+        value * self.get()
+    }
+}
+
+/// Filters for effects whose intensity may be out of date.
+type OutdatedIntensity = Or<(Changed<Lifetime>, Added<Intensity>)>;
+
+pub(super) fn update_intensity(mut query: Query<(&mut Intensity, &Lifetime), OutdatedIntensity>) {
+    let _span = debug_span!("update_intensity").entered();
+
+    query.par_iter_mut().for_each(|(mut intensity, lifetime)| {
+        intensity.update(lifetime);
+    });
+}
+
+/// Re-evaluates the effect's intensity, after its lifetime was merged with a replaced effect.
+pub(crate) fn refresh_intensity(world: &mut DeferredWorld, effect: Entity) {
+    let Some(lifetime) = world.get::<Lifetime>(effect).cloned() else {
+        return;
+    };
+
+    if let Some(mut intensity) = world.get_mut::<Intensity>(effect) {
+        intensity.update(&lifetime);
+    }
+}
@@ -1,42 +1,243 @@
 //! Relationship-based status effects for bevy.
 
+mod apply;
+mod batch;
+#[cfg(feature = "asset")]
+mod definition;
+#[cfg(feature = "bevy_diagnostic")]
+mod diagnostic;
+mod dynamic;
+mod error;
 mod hook;
+mod id;
+mod index;
+mod inspect;
+mod intensity;
+mod migration;
+mod reflect;
 mod relation;
+#[cfg(feature = "bevy_remote")]
+mod remote;
+#[cfg(feature = "serde")]
+mod save;
+mod sequence;
+mod snapshot;
+mod stat;
+mod step;
+mod tick;
 mod timer;
+mod transfer;
+mod turn;
+mod validate;
 
+use apply::tick_effect_cooldowns;
 use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::intern::Interned;
 use bevy_ecs::prelude::*;
+use bevy_ecs::schedule::ScheduleLabel;
 use bevy_reflect::prelude::ReflectDefault;
 use bevy_reflect::{Reflect, reflect_trait};
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy_time::{Fixed, Real, Virtual};
+use hook::{effect_mode_insert_hook, effect_mode_replace_hook, init_effect_index};
+use intensity::update_intensity;
 
+pub use apply::*;
+pub use batch::*;
 pub use bevy_status_effects_macros::StatusEffect;
+#[cfg(feature = "asset")]
+pub use definition::*;
+#[cfg(feature = "bevy_diagnostic")]
+pub use diagnostic::*;
+pub use dynamic::*;
+pub use error::*;
 pub use hook::*;
+pub use id::*;
+pub use index::*;
+pub use inspect::*;
+pub use intensity::*;
+pub use migration::*;
+pub use reflect::*;
 pub use relation::*;
+#[cfg(feature = "bevy_remote")]
+pub use remote::*;
+#[cfg(feature = "serde")]
+pub use save::*;
+pub use sequence::*;
+pub use snapshot::*;
+pub use stat::*;
+pub use step::*;
+pub use tick::*;
 pub use timer::*;
+pub use transfer::*;
+pub use turn::*;
+pub use validate::*;
 
 #[doc(hidden)]
 pub use bevy_app::Startup as __Startup;
 
-pub struct StatusEffectPlugin;
+/// Registers the status effect types and adds the systems that tick effect timers.
+pub struct StatusEffectPlugin {
+    /// The schedule that effect timers are ticked in. Defaults to [`PreUpdate`].
+    pub schedule: Interned<dyn ScheduleLabel>,
+    /// The clock used to tick effect timers. Defaults to [`EffectClock::Default`].
+    pub clock: EffectClock,
+}
+
+impl StatusEffectPlugin {
+    /// Creates a plugin that ticks effect timers in the given schedule.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+            clock: EffectClock::default(),
+        }
+    }
+
+    /// A builder that overwrites the current clock with a new value.
+    pub fn with_clock(mut self, clock: EffectClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn add_timer_systems<C: Default + Send + Sync + 'static>(&self, app: &mut App) {
+        app.add_systems(
+            self.schedule,
+            (
+                despawn_finished_lifetimes::<C>,
+                update_intensity,
+                tick_delay::<C>,
+                tick_effect_cooldowns::<C>,
+                tick_simulation_timers,
+            )
+                .chain()
+                .in_set(StatusEffectSystems::TickTimers),
+        )
+        .add_systems(
+            self.schedule,
+            log_effect_snapshot
+                .after(StatusEffectSystems::TickTimers)
+                .run_if(resource_equals(LogEffectSnapshot(true))),
+        );
+    }
+}
+
+impl Default for StatusEffectPlugin {
+    fn default() -> Self {
+        Self::new(PreUpdate)
+    }
+}
 
 impl Plugin for StatusEffectPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<EffectMode>()
             .register_type::<Effecting>()
             .register_type::<EffectedBy>()
+            .register_type::<EffectId>()
+            .register_type::<EffectSequence>()
+            .register_type::<EffectSequenceCounter>()
+            .register_type::<AppliedAt>()
+            .register_type::<EffectSource>()
+            .register_type::<SnapshotMergeMode>()
             .register_type::<Lifetime>()
             .register_type::<Delay>()
             .register_type::<TimerMergeMode>()
-            .add_systems(PreUpdate, (despawn_finished_lifetimes, tick_delay).chain());
+            .register_type::<TurnLifetime>()
+            .register_type::<TurnDelay>()
+            .register_type::<SimulationTick>()
+            .register_type::<TickLifetime>()
+            .register_type::<TickDelay>()
+            .register_type::<EffectKey>()
+            .register_type::<EffectImmunity>()
+            .register_type::<EffectStackLimit>()
+            .register_type::<EffectCooldowns>()
+            .register_type::<IntensityCurve>()
+            .register_type::<Intensity>()
+            .register_type::<DynamicEffects>()
+            .init_resource::<SimulationTick>()
+            .init_resource::<EffectRegistry>()
+            .init_resource::<EffectMigrations>()
+            .init_resource::<DynamicEffectRegistry>()
+            .init_resource::<LogEffectSnapshot>()
+            .add_observer(advance_turn);
+
+        app.insert_resource(self.clock);
+        init_effect_index(app.world_mut());
+
+        match self.clock {
+            EffectClock::Default => self.add_timer_systems::<()>(app),
+            EffectClock::Virtual => self.add_timer_systems::<Virtual>(app),
+            EffectClock::Real => self.add_timer_systems::<Real>(app),
+            EffectClock::Fixed => self.add_timer_systems::<Fixed>(app),
+        }
     }
 }
 
+/// The clock that is used to tick [`Lifetime`] and [`Delay`] timers.
+///
+/// Inserted as a resource by the [`StatusEffectPlugin`], so that [`AppliedAt`] is recorded using the same clock.
+#[derive(Resource, Eq, PartialEq, Debug, Default, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EffectClock {
+    /// Uses the default [`Time`](bevy_time::Time), which is virtual time in most schedules
+    /// and fixed time in [`FixedMain`](bevy_app::FixedMain) schedules.
+    #[default]
+    Default,
+    /// Uses [`Time<Virtual>`](Virtual), which can be paused and scaled.
+    Virtual,
+    /// Uses [`Time<Real>`](Real), which ignores pausing and scaling.
+    Real,
+    /// Uses [`Time<Fixed>`](Fixed). Should be used alongside a fixed timestep schedule.
+    Fixed,
+}
+
+/// System sets used by the [`StatusEffectPlugin`].
+#[derive(SystemSet, Eq, PartialEq, Hash, Debug, Copy, Clone)]
+pub enum StatusEffectSystems {
+    /// Ticks [`Lifetime`], [`Delay`], [`TickLifetime`], and [`TickDelay`] timers,
+    /// despawning any effects that have finished. Also ticks [`EffectCooldowns`] and updates [`Intensity`].
+    TickTimers,
+    /// Updates the [`StatValue`]s of entities whose effects have changed. Added by each [`StatPlugin`].
+    UpdateStats,
+}
+
+/// A marker trait for status effect components.
 #[reflect_trait]
-pub trait StatusEffect {}
+pub trait StatusEffect {
+    /// Returns the effect's stable [`EffectId`], which is set using `#[status_effect(id = "...")]`.
+    fn id() -> Option<EffectId>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Returns the version of the effect's data, which is set using `#[status_effect(version = N)]`.
+    /// Used to [migrate](EffectMigrations) effects from old saves.
+    fn version() -> u32
+    where
+        Self: Sized,
+    {
+        0
+    }
+}
 
 /// Describes the logic used when multiple of the same effect are applied to the same entity.
+///
+/// The mode is re-evaluated whenever it is inserted, so it is immutable.
+/// To change the mode of an existing effect, use [`EntityCommands::insert`] or [`World::modify_component`].
 #[derive(Component, Reflect, Eq, PartialEq, Debug, Default, Copy, Clone)]
+#[component(
+    immutable,
+    on_insert = effect_mode_insert_hook,
+    on_replace = effect_mode_replace_hook
+)]
 #[reflect(Component, PartialEq, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub enum EffectMode {
     /// Multiple of the same effect can exist at once.
     #[default]
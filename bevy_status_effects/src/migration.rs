@@ -0,0 +1,351 @@
+use crate::dynamic::{DynamicEffectRegistry, DynamicEffects, spawn_dynamic_data};
+use crate::id::{EffectId, EffectRegistry};
+use crate::reflect::{ReflectEffectError, spawn_reflected_effect};
+use crate::relation::Effecting;
+use bevy_ecs::entity::EntityMapper;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::{AppTypeRegistry, ReflectComponent};
+use bevy_ecs::relationship::RelationshipHookMode;
+use bevy_reflect::PartialReflect;
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+use tracing::warn;
+
+/// Upgrades an effect's data by a single version.
+pub type EffectMigration =
+    Box<dyn Fn(Box<dyn PartialReflect>) -> Box<dyn PartialReflect> + Send + Sync>;
+
+/// A status effect that has been saved, along with the version of its data.
+///
+/// The data is usually a dynamic type, such as [`DynamicStruct`](bevy_reflect::DynamicStruct),
+/// since the fields of the effect may have changed since it was saved.
+/// With the `serde` feature, it can be written to and read from a save file
+/// using [`SavedEffectSerializer`](crate::SavedEffectSerializer) and [`SavedEffectDeserializer`](crate::SavedEffectDeserializer).
+#[derive(Debug)]
+pub struct SavedEffect {
+    /// The stable id of the effect.
+    pub id: EffectId,
+    /// The version of the effect when it was saved.
+    pub version: u32,
+    /// The reflected data of the effect.
+    pub data: Box<dyn PartialReflect>,
+    /// The entity that the effect was [effecting](Effecting) when it was saved.
+    ///
+    /// Entities change between sessions, so this should be mapped to the new target when the effect is loaded.
+    pub target: Option<Entity>,
+    /// The effect's other reflected components, such as its [`Lifetime`](crate::Lifetime) and [`EffectMode`](crate::EffectMode).
+    ///
+    /// Unlike the data, these aren't migrated, so they must match the current version of their types.
+    pub components: Vec<Box<dyn PartialReflect>>,
+}
+
+impl SavedEffect {
+    /// Creates a saved effect from its data, without a target or any other components.
+    pub fn new(id: impl Into<EffectId>, version: u32, data: Box<dyn PartialReflect>) -> Self {
+        Self {
+            id: id.into(),
+            version,
+            data,
+            target: None,
+            components: Vec::new(),
+        }
+    }
+}
+
+/// Stores the migrations used to upgrade [saved effects](SavedEffect) to their current version.
+#[derive(Resource, Default)]
+pub struct EffectMigrations {
+    migrations: HashMap<(EffectId, u32), EffectMigration>,
+}
+
+impl EffectMigrations {
+    /// Adds a migration, which upgrades the effect's data from `version` to `version + 1`.
+    pub fn add(
+        &mut self,
+        id: impl Into<EffectId>,
+        version: u32,
+        migration: impl Fn(Box<dyn PartialReflect>) -> Box<dyn PartialReflect> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.migrations
+            .insert((id.into(), version), Box::new(migration));
+        self
+    }
+
+    /// Upgrades the effect's data from one version to another, by running each migration in order.
+    pub fn migrate(
+        &self,
+        id: &EffectId,
+        from: u32,
+        to: u32,
+        mut data: Box<dyn PartialReflect>,
+    ) -> Result<Box<dyn PartialReflect>, LoadEffectError> {
+        if from > to {
+            return Err(LoadEffectError::NewerVersion {
+                id: id.clone(),
+                saved: from,
+                current: to,
+            });
+        }
+
+        for version in from..to {
+            let migration = self.migrations.get(&(id.clone(), version)).ok_or_else(|| {
+                LoadEffectError::MissingMigration {
+                    id: id.clone(),
+                    version,
+                }
+            })?;
+
+            data = migration(data);
+        }
+
+        Ok(data)
+    }
+}
+
+/// An error that can occur when loading a [`SavedEffect`].
+#[derive(Error, Debug)]
+pub enum LoadEffectError {
+    /// No effect has been registered with the id, likely because it was removed.
+    #[error("no effect is registered with the id `{0}`")]
+    UnknownId(EffectId),
+    /// The effect was saved with a newer version than the current one.
+    #[error(
+        "effect `{id}` was saved with version {saved}, which is newer than the current version {current}"
+    )]
+    NewerVersion {
+        /// The id of the effect.
+        id: EffectId,
+        /// The version of the saved effect.
+        saved: u32,
+        /// The current version of the effect.
+        current: u32,
+    },
+    /// There is no migration from the version.
+    #[error("effect `{id}` has no migration from version {version}")]
+    MissingMigration {
+        /// The id of the effect.
+        id: EffectId,
+        /// The version without a migration.
+        version: u32,
+    },
+    /// The effect couldn't be spawned.
+    #[error(transparent)]
+    Reflect(#[from] ReflectEffectError),
+}
+
+/// The result of [`load_effects`].
+#[derive(Debug, Default)]
+pub struct LoadReport {
+    /// The effects that were successfully loaded.
+    pub loaded: Vec<Entity>,
+    /// The effects that were dropped, and why.
+    pub dropped: Vec<(EffectId, LoadEffectError)>,
+}
+
+/// Saves all effects on the entity that have a stable [`EffectId`], including each kind of [`DynamicEffect`](crate::DynamicEffect).
+///
+/// Along with the effect's data, its target and any other components that are registered with `#[reflect(Component)]` are saved,
+/// except for [`Effecting`] and other effect types.
+/// This requires the effect types to be registered with `#[reflect(Component)]`.
+///
+/// Saved components may reference entities, such as an [`EffectSource`](crate::EffectSource).
+/// These are mapped to their new entities when the effect is loaded, if the component maps them in [`Component::map_entities`].
+pub fn save_effects(world: &World, entity: Entity) -> Vec<SavedEffect> {
+    let (Some(type_registry), Ok(entity)) = (
+        world.get_resource::<AppTypeRegistry>(),
+        world.get_entity(entity),
+    ) else {
+        return Vec::new();
+    };
+    let type_registry = type_registry.read();
+    let registry = world.get_resource::<EffectRegistry>();
+
+    let mut skipped: HashSet<TypeId> = registry
+        .into_iter()
+        .flat_map(EffectRegistry::iter)
+        .map(|registration| registration.type_id)
+        .collect();
+    skipped.insert(TypeId::of::<Effecting>());
+    skipped.insert(TypeId::of::<DynamicEffects>());
+
+    let components: Vec<Box<dyn PartialReflect>> = entity
+        .archetype()
+        .components()
+        .filter_map(|id| world.components().get_info(id)?.type_id())
+        .filter(|type_id| !skipped.contains(type_id))
+        .filter_map(|type_id| type_registry.get_type_data::<ReflectComponent>(type_id))
+        .filter_map(|reflect_component| reflect_component.reflect(entity))
+        .map(|component| component.to_dynamic())
+        .collect();
+    let target = entity.get::<Effecting>().map(|effecting| effecting.0);
+
+    let saved = |id: EffectId, version: u32, data: &dyn PartialReflect| SavedEffect {
+        id,
+        version,
+        data: data.to_dynamic(),
+        target,
+        components: components
+            .iter()
+            .map(|component| component.to_dynamic())
+            .collect(),
+    };
+
+    let compiled = registry
+        .into_iter()
+        .flat_map(EffectRegistry::iter)
+        .filter_map(|registration| {
+            let id = registration.id.clone()?;
+            let reflect_component =
+                type_registry.get_type_data::<ReflectComponent>(registration.type_id)?;
+            let data = reflect_component.reflect(entity)?;
+
+            Some(saved(id, registration.version, data.as_partial_reflect()))
+        });
+
+    let dynamic_registry = world.get_resource::<DynamicEffectRegistry>();
+    let dynamic = entity
+        .get::<DynamicEffects>()
+        .into_iter()
+        .flat_map(DynamicEffects::iter)
+        .filter_map(|effect| {
+            let info = dynamic_registry?.get(effect.kind())?;
+            Some(saved(effect.kind().clone(), info.version, &effect.data))
+        });
+
+    compiled.chain(dynamic).collect()
+}
+
+/// Migrates the saved effect to its current version, and then spawns it along with a bundle,
+/// which should contain [`Effecting`] for the new target.
+///
+/// The id is looked up in the [`EffectRegistry`], and then in the [`DynamicEffectRegistry`].
+///
+/// Unlike the other ways of spawning an effect, the target doesn't [accept](crate::can_apply_effect) the effect
+/// and [`ApplyingEffect`](crate::ApplyingEffect) isn't triggered, since the effect was already applied before it was saved.
+/// Its snapshots are restored from the saved components instead of being taken again.
+///
+/// The saved components are inserted afterwards, with any entities they reference mapped using the entity mapper,
+/// such as an [`EntityHashMap`](bevy_ecs::entity::EntityHashMap) from saved entities to their new entities.
+pub fn load_effect(
+    world: &mut World,
+    saved: SavedEffect,
+    bundle: impl Bundle,
+    entity_mapper: &mut dyn EntityMapper,
+) -> Result<Entity, LoadEffectError> {
+    let compiled = world
+        .get_resource::<EffectRegistry>()
+        .and_then(|registry| registry.get(&saved.id))
+        .map(|registration| {
+            (
+                Some((registration.type_id, registration.type_name)),
+                registration.version,
+            )
+        });
+    let (compiled, version) = compiled
+        .or_else(|| {
+            world
+                .get_resource::<DynamicEffectRegistry>()
+                .and_then(|registry| registry.get(&saved.id))
+                .map(|info| (None, info.version))
+        })
+        .ok_or_else(|| LoadEffectError::UnknownId(saved.id.clone()))?;
+
+    let data = if saved.version == version {
+        saved.data
+    } else {
+        match world.get_resource::<EffectMigrations>() {
+            Some(migrations) => {
+                migrations.migrate(&saved.id, saved.version, version, saved.data)?
+            }
+            None => {
+                return Err(LoadEffectError::MissingMigration {
+                    id: saved.id,
+                    version: saved.version,
+                });
+            }
+        }
+    };
+
+    let components = reflect_components(world, &saved.components)?;
+    let effect = match compiled {
+        Some((type_id, type_name)) => {
+            spawn_reflected_effect(world, type_id, type_name, Some(data.as_ref()), bundle)?
+        }
+        None => spawn_dynamic_data(world, &saved.id, Some(data.as_ref()), bundle)?,
+    };
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let mut entity = world.entity_mut(effect);
+
+    for (component, reflect_component) in components {
+        reflect_component.apply_or_insert_mapped(
+            &mut entity,
+            component,
+            &type_registry,
+            entity_mapper,
+            RelationshipHookMode::Run,
+        );
+    }
+
+    Ok(effect)
+}
+
+/// Looks up the [`ReflectComponent`] of each saved component, before the effect is spawned.
+///
+/// Components that aren't registered with `#[reflect(Component)]` are skipped with a warning.
+fn reflect_components<'a>(
+    world: &World,
+    components: &'a [Box<dyn PartialReflect>],
+) -> Result<Vec<(&'a dyn PartialReflect, ReflectComponent)>, ReflectEffectError> {
+    let type_registry = world
+        .get_resource::<AppTypeRegistry>()
+        .ok_or(ReflectEffectError::MissingTypeRegistry)?
+        .read();
+
+    Ok(components
+        .iter()
+        .filter_map(|component| {
+            let reflect_component = component
+                .get_represented_type_info()
+                .and_then(|type_info| {
+                    type_registry.get_type_data::<ReflectComponent>(type_info.type_id())
+                });
+
+            if reflect_component.is_none() {
+                warn!(
+                    "Skipped saved component `{}`, since it isn't registered with `#[reflect(Component)]`",
+                    component.reflect_type_path()
+                );
+            }
+
+            Some((component.as_ref(), reflect_component?.clone()))
+        })
+        .collect())
+}
+
+/// Loads multiple saved effects using [`load_effect`], mapping their entities with the same entity mapper.
+///
+/// Unlike [`load_effect`], this never fails. Instead, any effects that can't be loaded are dropped and logged as a warning.
+pub fn load_effects<B: Bundle>(
+    world: &mut World,
+    effects: impl IntoIterator<Item = (SavedEffect, B)>,
+    entity_mapper: &mut dyn EntityMapper,
+) -> LoadReport {
+    let mut report = LoadReport::default();
+
+    for (saved, bundle) in effects {
+        let id = saved.id.clone();
+
+        match load_effect(world, saved, bundle, entity_mapper) {
+            Ok(entity) => report.loaded.push(entity),
+            Err(error) => {
+                warn!("Dropped saved effect `{id}`: {error}");
+                report.dropped.push((id, error));
+            }
+        }
+    }
+
+    report
+}
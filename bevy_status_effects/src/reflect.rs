@@ -0,0 +1,186 @@
+use crate::apply::accept_effect;
+use crate::error::StatusEffectError;
+use crate::{EffectId, ReflectStatusEffect};
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::{AppTypeRegistry, ReflectComponent};
+use bevy_reflect::prelude::ReflectDefault;
+use bevy_reflect::{ApplyError, PartialReflect, ReflectFromReflect};
+use std::any::TypeId;
+use thiserror::Error;
+
+/// An error that can occur when spawning an effect using reflection.
+#[derive(Error, Debug)]
+pub enum ReflectEffectError {
+    /// No effect has been registered with the id.
+    #[error("no effect is registered with the id `{0}`")]
+    UnknownId(EffectId),
+    /// The world doesn't contain an [`AppTypeRegistry`].
+    #[error("the world doesn't contain an `AppTypeRegistry`")]
+    MissingTypeRegistry,
+    /// The effect type isn't registered in the [`AppTypeRegistry`].
+    #[error("`{0}` is not registered in the `AppTypeRegistry`")]
+    NotRegistered(String),
+    /// The type is registered, but isn't registered with `#[reflect(StatusEffect)]`.
+    #[error("`{0}` is not registered with `#[reflect(StatusEffect)]`")]
+    NotStatusEffect(String),
+    /// The effect type is registered, but is missing some required type data.
+    #[error("`{type_path}` doesn't reflect `{data}`")]
+    MissingTypeData {
+        /// The type path of the effect.
+        type_path: String,
+        /// The name of the missing type data.
+        data: &'static str,
+    },
+    /// A field of a [`DynamicEffect`](crate::DynamicEffect)'s data is a different kind of value than its default.
+    #[error("the field `{field}` of `{kind}` must be a `{expected}`")]
+    DynamicField {
+        /// The kind of the dynamic effect.
+        kind: EffectId,
+        /// The name of the field.
+        field: String,
+        /// The kind of value that the field's default is.
+        expected: &'static str,
+    },
+    /// The provided data couldn't be applied to the effect.
+    #[error("failed to apply data to the effect: {0}")]
+    Apply(#[from] ApplyError),
+    /// The effect was spawned, but the target rejected it.
+    #[error(transparent)]
+    Rejected(#[from] StatusEffectError),
+}
+
+/// Spawns an effect using its type path or short type path (such as `Burning`), along with a bundle,
+/// and then applies it to the target the same way as [`apply_effect`](crate::apply_effect).
+///
+/// The effect is created using its reflected [`Default`] implementation, and then `data` is applied on top of it.
+/// If the effect doesn't reflect `Default`, it is instead created from `data` using `FromReflect`.
+/// This requires the effect type to be registered with `#[reflect(Component, StatusEffect)]`.
+///
+/// The bundle shouldn't contain [`Effecting`](crate::Effecting), since it is added once the effect has been accepted.
+pub fn spawn_effect_by_name(
+    world: &mut World,
+    target: Entity,
+    name: &str,
+    data: Option<&dyn PartialReflect>,
+    bundle: impl Bundle,
+) -> Result<Entity, ReflectEffectError> {
+    let type_id = {
+        let type_registry = world
+            .get_resource::<AppTypeRegistry>()
+            .ok_or(ReflectEffectError::MissingTypeRegistry)?
+            .read();
+
+        let registration = type_registry
+            .get_with_type_path(name)
+            .or_else(|| type_registry.get_with_short_type_path(name))
+            .ok_or_else(|| ReflectEffectError::NotRegistered(name.to_string()))?;
+
+        if registration.data::<ReflectStatusEffect>().is_none() {
+            return Err(ReflectEffectError::NotStatusEffect(name.to_string()));
+        }
+
+        registration.type_id()
+    };
+
+    apply_reflected_effect(world, target, type_id, name, data, bundle)
+}
+
+/// A [`Command`] that spawns an effect using [`spawn_effect_by_name`],
+/// and applies it to the target.
+pub struct SpawnEffectByName {
+    /// The type path or short type path of the effect.
+    pub name: String,
+    /// Data that is applied to the effect, if any.
+    pub data: Option<Box<dyn PartialReflect>>,
+    /// The entity that the effect will be effecting.
+    pub target: Entity,
+}
+
+impl Command<Result> for SpawnEffectByName {
+    fn apply(self, world: &mut World) -> Result {
+        spawn_effect_by_name(world, self.target, &self.name, self.data.as_deref(), ())?;
+        Ok(())
+    }
+}
+
+/// Spawns an effect of the given type using [`spawn_reflected_effect`], and then applies it to the target.
+pub(crate) fn apply_reflected_effect(
+    world: &mut World,
+    target: Entity,
+    type_id: TypeId,
+    type_name: &str,
+    data: Option<&dyn PartialReflect>,
+    bundle: impl Bundle,
+) -> Result<Entity, ReflectEffectError> {
+    check_target(world, target)?;
+
+    let effect = spawn_reflected_effect(world, type_id, type_name, data, bundle)?;
+    accept_effect(world, effect, target)?;
+    Ok(effect)
+}
+
+/// Returns an error if the target doesn't exist, so that effects aren't spawned for it.
+pub(crate) fn check_target(world: &World, target: Entity) -> Result<(), StatusEffectError> {
+    match world.get_entity(target) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(StatusEffectError::InvalidTarget(target)),
+    }
+}
+
+/// Spawns an effect of the given type, using its reflected [`Default`] or [`FromReflect`](bevy_reflect::FromReflect) implementation.
+/// If provided, `data` is then applied on top of the default value.
+///
+/// The bundle is spawned before the effect component is inserted, so the effect hook can see it.
+pub(crate) fn spawn_reflected_effect(
+    world: &mut World,
+    type_id: TypeId,
+    type_name: &str,
+    data: Option<&dyn PartialReflect>,
+    bundle: impl Bundle,
+) -> Result<Entity, ReflectEffectError> {
+    let type_registry = world
+        .get_resource::<AppTypeRegistry>()
+        .ok_or(ReflectEffectError::MissingTypeRegistry)?
+        .clone();
+    let type_registry = type_registry.read();
+
+    let registration = type_registry
+        .get(type_id)
+        .ok_or_else(|| ReflectEffectError::NotRegistered(type_name.to_string()))?;
+    let type_path = registration.type_info().type_path();
+
+    let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+        ReflectEffectError::MissingTypeData {
+            type_path: type_path.to_string(),
+            data: "Component",
+        }
+    })?;
+
+    let effect = match (registration.data::<ReflectDefault>(), data) {
+        (Some(reflect_default), data) => {
+            let mut effect = reflect_default.default();
+            if let Some(data) = data {
+                effect.try_apply(data)?;
+            }
+            effect
+        }
+        (None, Some(data)) => registration
+            .data::<ReflectFromReflect>()
+            .and_then(|reflect_from_reflect| reflect_from_reflect.from_reflect(data))
+            .ok_or_else(|| ReflectEffectError::MissingTypeData {
+                type_path: type_path.to_string(),
+                data: "Default",
+            })?,
+        (None, None) => {
+            return Err(ReflectEffectError::MissingTypeData {
+                type_path: type_path.to_string(),
+                data: "Default",
+            });
+        }
+    };
+
+    let mut entity = world.spawn(bundle);
+    reflect_component.insert(&mut entity, effect.as_partial_reflect(), &type_registry);
+
+    Ok(entity.id())
+}
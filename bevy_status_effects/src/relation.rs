@@ -1,17 +1,31 @@
 use crate::ReflectComponent;
+use crate::sequence::{AppliedAt, EffectSequence};
 use bevy_ecs::prelude::{Component, Entity};
 use bevy_reflect::Reflect;
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 
 /// Stores the entity that is being effected by this status effect.
 #[derive(Component, Reflect, Eq, PartialEq, Debug, Clone)]
 #[relationship(relationship_target = EffectedBy)]
+#[require(EffectSequence, AppliedAt)]
 #[reflect(Component, PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub struct Effecting(pub Entity);
 
 /// Stores all the status effects that are effecting this entity.
 #[derive(Component, Reflect, Eq, PartialEq, Debug, Clone)]
 #[relationship_target(relationship = Effecting, linked_spawn)]
 #[reflect(Component, PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub struct EffectedBy(Vec<Entity>);
 
 impl<'a> IntoIterator for &'a EffectedBy {
@@ -0,0 +1,423 @@
+use crate::EffectMode;
+use crate::apply::accept_effect;
+use crate::dynamic::{
+    DynamicData, DynamicEffect, DynamicEffectRegistry, DynamicEffects, spawn_dynamic_data,
+};
+use crate::id::{EffectId, EffectRegistry};
+use crate::reflect::{ReflectEffectError, spawn_reflected_effect};
+use crate::relation::EffectedBy;
+use crate::tick::TickLifetime;
+use crate::timer::{EffectTimer, Lifetime};
+use crate::turn::TurnLifetime;
+use bevy_ecs::component::ComponentId;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_reflect::serde::TypedReflectDeserializer;
+use bevy_reflect::{PartialReflect, TypeRegistry};
+use bevy_remote::{BrpError, BrpResult, RemotePlugin, error_codes};
+use serde::de::DeserializeSeed;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::any::{TypeId, type_name};
+use std::time::Duration;
+
+/// The method path for a `status_effects/list` request.
+pub const BRP_LIST_EFFECTS_METHOD: &str = "status_effects/list";
+
+/// The method path for a `status_effects/apply` request.
+pub const BRP_APPLY_EFFECT_METHOD: &str = "status_effects/apply";
+
+/// The method path for a `status_effects/remove` request.
+pub const BRP_REMOVE_EFFECTS_METHOD: &str = "status_effects/remove";
+
+/// Adds the status effect methods to a [`RemotePlugin`].
+pub trait StatusEffectRemoteMethods {
+    /// Adds the `status_effects/list`, `status_effects/apply`, and `status_effects/remove` methods.
+    fn with_status_effect_methods(self) -> Self;
+}
+
+impl StatusEffectRemoteMethods for RemotePlugin {
+    fn with_status_effect_methods(self) -> Self {
+        self.with_method(BRP_LIST_EFFECTS_METHOD, process_list_effects_request)
+            .with_method(BRP_APPLY_EFFECT_METHOD, process_apply_effect_request)
+            .with_method(BRP_REMOVE_EFFECTS_METHOD, process_remove_effects_request)
+    }
+}
+
+/// The parameters of a `status_effects/list` request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BrpListEffectsParams {
+    /// The entity whose effects are listed.
+    pub entity: Entity,
+}
+
+/// The parameters of a `status_effects/apply` request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BrpApplyEffectParams {
+    /// The entity that the effect will be effecting.
+    pub target: Entity,
+    /// The type path, short type path, or [`EffectId`] of the effect.
+    pub effect: String,
+    /// The serialized effect, which is applied on top of its default value.
+    ///
+    /// For a [`DynamicEffect`], this is an object containing any fields that should be overwritten.
+    #[serde(default)]
+    pub data: Option<Value>,
+    /// The mode of the effect. Defaults to [`EffectMode::Stack`],
+    /// or the registered mode of a [`DynamicEffect`].
+    #[serde(default)]
+    pub mode: Option<EffectMode>,
+    /// The [`Lifetime`] of the effect, in seconds.
+    #[serde(default)]
+    pub lifetime: Option<f32>,
+}
+
+/// The parameters of a `status_effects/remove` request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BrpRemoveEffectsParams {
+    /// The entity whose effects are removed.
+    pub target: Entity,
+    /// The type path, short type path, or [`EffectId`] of the effects to remove.
+    /// If not set, all effects are removed.
+    #[serde(default)]
+    pub effect: Option<String>,
+}
+
+/// A single effect in the response of a `status_effects/list` request.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct BrpEffect {
+    /// The effect entity.
+    pub entity: Entity,
+    /// The type path of the effect component.
+    /// Not set if the entity doesn't contain a registered effect component.
+    pub type_path: Option<String>,
+    /// The [`EffectId`] of the effect, or the kind of a [`DynamicEffect`].
+    pub id: Option<EffectId>,
+    /// The mode of the effect.
+    pub mode: Option<EffectMode>,
+    /// The remaining [`Lifetime`] of the effect, in seconds.
+    pub lifetime: Option<f32>,
+    /// The remaining [`TurnLifetime`] of the effect.
+    pub turns: Option<u32>,
+    /// The remaining [`TickLifetime`] of the effect.
+    pub ticks: Option<u32>,
+}
+
+/// The response of a `status_effects/apply` request.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct BrpApplyEffectResponse {
+    /// The spawned effect entity.
+    pub entity: Entity,
+}
+
+/// Handles a `status_effects/list` request, returning a list of [`BrpEffect`]s.
+pub fn process_list_effects_request(In(params): In<Option<Value>>, world: &World) -> BrpResult {
+    let BrpListEffectsParams { entity } = parse_some(params)?;
+
+    if world.get_entity(entity).is_err() {
+        return Err(BrpError::entity_not_found(entity));
+    }
+
+    let type_registry = world
+        .get_resource::<AppTypeRegistry>()
+        .map(|registry| registry.read());
+
+    let mut response = Vec::new();
+
+    let Some(effected_by) = world.get::<EffectedBy>(entity) else {
+        return serde_json::to_value(response).map_err(BrpError::internal);
+    };
+
+    for effect in effected_by {
+        let Ok(effect_ref) = world.get_entity(*effect) else {
+            continue;
+        };
+
+        let template = BrpEffect {
+            entity: *effect,
+            type_path: None,
+            id: None,
+            mode: effect_ref.get::<EffectMode>().copied(),
+            lifetime: effect_ref
+                .get::<Lifetime>()
+                .map(|lifetime| lifetime.timer.remaining_secs()),
+            turns: effect_ref
+                .get::<TurnLifetime>()
+                .map(|lifetime| lifetime.timer.remaining()),
+            ticks: effect_ref
+                .get::<TickLifetime>()
+                .map(|lifetime| lifetime.timer.remaining()),
+        };
+
+        let mut found = false;
+
+        if let Some(registry) = world.get_resource::<EffectRegistry>() {
+            for registration in registry.iter() {
+                if !effect_ref.contains_id(registration.component_id) {
+                    continue;
+                }
+
+                let type_path = type_registry
+                    .as_ref()
+                    .and_then(|type_registry| type_registry.get(registration.type_id))
+                    .map(|type_registration| type_registration.type_info().type_path())
+                    .unwrap_or(registration.type_name);
+
+                found = true;
+                response.push(BrpEffect {
+                    type_path: Some(type_path.to_string()),
+                    id: registration.id.clone(),
+                    ..template.clone()
+                });
+            }
+        }
+
+        for kind in effect_ref
+            .get::<DynamicEffects>()
+            .into_iter()
+            .flat_map(DynamicEffects::kinds)
+        {
+            found = true;
+            response.push(BrpEffect {
+                type_path: Some(type_name::<DynamicEffect>().to_string()),
+                id: Some(kind.clone()),
+                ..template.clone()
+            });
+        }
+
+        if !found {
+            response.push(template);
+        }
+    }
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `status_effects/apply` request, returning a [`BrpApplyEffectResponse`].
+///
+/// The effect is applied the same way as [`apply_effect`](crate::apply_effect), so it can be rejected by the target or cancelled by an observer.
+pub fn process_apply_effect_request(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let BrpApplyEffectParams {
+        target,
+        effect,
+        data,
+        mode,
+        lifetime,
+    } = parse_some(params)?;
+
+    if world.get_entity(target).is_err() {
+        return Err(BrpError::entity_not_found(target));
+    }
+
+    let kind = resolve_effect(world, &effect)?;
+
+    let mode = match (&kind, mode) {
+        (_, Some(mode)) => mode,
+        (EffectKind::Dynamic(id), None) => world
+            .resource::<DynamicEffectRegistry>()
+            .get(id)
+            .map(|info| info.mode)
+            .unwrap_or_default(),
+        (EffectKind::Compiled { .. }, None) => EffectMode::default(),
+    };
+
+    let data = match data {
+        Some(data) => Some(deserialize_data(world, &kind, data)?),
+        None => None,
+    };
+
+    let lifetime = match lifetime {
+        Some(seconds) => Some(Lifetime::new(
+            Duration::try_from_secs_f32(seconds).map_err(|_| {
+                invalid_params(format!("`{seconds}` is not a valid lifetime in seconds"))
+            })?,
+        )),
+        None => None,
+    };
+
+    let entity = match lifetime {
+        Some(lifetime) => spawn_kind(world, &kind, data.as_deref(), (mode, lifetime)),
+        None => spawn_kind(world, &kind, data.as_deref(), mode),
+    }
+    .map_err(|error| match error {
+        ReflectEffectError::DynamicField { .. } => invalid_params(error.to_string()),
+        error => BrpError::component_error(error),
+    })?;
+
+    // Applied the same way as `apply_effect`, so the target can reject or intercept it.
+    accept_effect(world, entity, target).map_err(BrpError::component_error)?;
+
+    serde_json::to_value(BrpApplyEffectResponse { entity }).map_err(BrpError::internal)
+}
+
+/// Handles a `status_effects/remove` request, returning a list of the despawned effect entities.
+pub fn process_remove_effects_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpRemoveEffectsParams { target, effect } = parse_some(params)?;
+
+    if world.get_entity(target).is_err() {
+        return Err(BrpError::entity_not_found(target));
+    }
+
+    let kind = match effect {
+        Some(effect) => Some(resolve_effect(world, &effect)?),
+        None => None,
+    };
+
+    let effects: Vec<Entity> = world
+        .get::<EffectedBy>(target)
+        .map(|effected_by| effected_by.into_iter().copied().collect())
+        .unwrap_or_default();
+
+    let removed: Vec<Entity> = effects
+        .into_iter()
+        .filter(|effect| {
+            let Ok(effect_ref) = world.get_entity(*effect) else {
+                return false;
+            };
+
+            match &kind {
+                None => true,
+                Some(EffectKind::Compiled { component_id, .. }) => {
+                    effect_ref.contains_id(*component_id)
+                }
+                Some(EffectKind::Dynamic(id)) => effect_ref
+                    .get::<DynamicEffects>()
+                    .is_some_and(|dynamic| dynamic.contains(id)),
+            }
+        })
+        .collect();
+
+    for effect in &removed {
+        world.despawn(*effect);
+    }
+
+    serde_json::to_value(removed).map_err(BrpError::internal)
+}
+
+/// An effect type that was found using its name.
+enum EffectKind {
+    Compiled {
+        type_id: TypeId,
+        type_name: &'static str,
+        component_id: ComponentId,
+    },
+    Dynamic(EffectId),
+}
+
+/// Finds an effect using its type path, short type path, or [`EffectId`].
+fn resolve_effect(world: &World, name: &str) -> Result<EffectKind, BrpError> {
+    let registry = world.get_resource::<EffectRegistry>();
+
+    let type_id = world
+        .get_resource::<AppTypeRegistry>()
+        .and_then(|type_registry| {
+            let type_registry = type_registry.read();
+            type_registry
+                .get_with_type_path(name)
+                .or_else(|| type_registry.get_with_short_type_path(name))
+                .map(|registration| registration.type_id())
+        });
+
+    let registration = type_id
+        .and_then(|type_id| registry?.get_by_type(type_id))
+        .or_else(|| registry?.get(&EffectId::from(name.to_string())));
+
+    if let Some(registration) = registration {
+        return Ok(EffectKind::Compiled {
+            type_id: registration.type_id,
+            type_name: registration.type_name,
+            component_id: registration.component_id,
+        });
+    }
+
+    let id = EffectId::from(name.to_string());
+    if world
+        .get_resource::<DynamicEffectRegistry>()
+        .is_some_and(|registry| registry.get(&id).is_some())
+    {
+        return Ok(EffectKind::Dynamic(id));
+    }
+
+    Err(BrpError::component_error(ReflectEffectError::UnknownId(id)))
+}
+
+/// Spawns an effect of the given kind, along with a bundle.
+fn spawn_kind(
+    world: &mut World,
+    kind: &EffectKind,
+    data: Option<&dyn PartialReflect>,
+    bundle: impl Bundle,
+) -> Result<Entity, ReflectEffectError> {
+    match kind {
+        EffectKind::Compiled {
+            type_id, type_name, ..
+        } => spawn_reflected_effect(world, *type_id, type_name, data, bundle),
+        EffectKind::Dynamic(id) => spawn_dynamic_data(world, id, data, bundle),
+    }
+}
+
+/// Deserializes the data of an effect using the type registry.
+///
+/// The data of a [`DynamicEffect`] is deserialized as [`DynamicData`], and may only contain fields that have defaults.
+fn deserialize_data(
+    world: &World,
+    kind: &EffectKind,
+    data: Value,
+) -> Result<Box<dyn PartialReflect>, BrpError> {
+    let type_registry = world
+        .get_resource::<AppTypeRegistry>()
+        .ok_or_else(|| BrpError::internal(ReflectEffectError::MissingTypeRegistry))?
+        .read();
+
+    match kind {
+        EffectKind::Compiled {
+            type_id, type_name, ..
+        } => deserialize_typed(&type_registry, *type_id, type_name, data),
+        EffectKind::Dynamic(id) => {
+            let data: DynamicData =
+                serde_json::from_value(data).map_err(|error| invalid_params(error.to_string()))?;
+
+            let registry = world.resource::<DynamicEffectRegistry>();
+            let defaults = &registry.get(id).expect("kind was resolved").defaults;
+
+            if let Some(name) = data.keys().find(|name| !defaults.contains_key(*name)) {
+                return Err(invalid_params(format!("`{id}` has no field `{name}`")));
+            }
+
+            Ok(Box::new(data))
+        }
+    }
+}
+
+/// Deserializes a value of the given type using the type registry.
+fn deserialize_typed(
+    type_registry: &TypeRegistry,
+    type_id: TypeId,
+    type_name: &str,
+    value: Value,
+) -> Result<Box<dyn PartialReflect>, BrpError> {
+    let registration = type_registry.get(type_id).ok_or_else(|| {
+        BrpError::component_error(ReflectEffectError::NotRegistered(type_name.to_string()))
+    })?;
+
+    TypedReflectDeserializer::new(registration, type_registry)
+        .deserialize(value)
+        .map_err(|error| invalid_params(error.to_string()))
+}
+
+/// Parses the parameters of a request, returning an error if they are missing or invalid.
+fn parse_some<T: for<'de> Deserialize<'de>>(params: Option<Value>) -> Result<T, BrpError> {
+    let params = params.ok_or_else(|| invalid_params("params not provided"))?;
+    serde_json::from_value(params).map_err(|error| invalid_params(error.to_string()))
+}
+
+fn invalid_params(message: impl Into<String>) -> BrpError {
+    BrpError {
+        code: error_codes::INVALID_PARAMS,
+        message: message.into(),
+        data: None,
+    }
+}
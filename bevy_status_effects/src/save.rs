@@ -0,0 +1,653 @@
+use crate::migration::SavedEffect;
+use bevy_reflect::serde::{
+    ReflectDeserializer, ReflectSerializer, TypedReflectDeserializer, TypedReflectSerializer,
+};
+use bevy_reflect::{
+    DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicSet, DynamicStruct, DynamicTuple,
+    DynamicTupleStruct, DynamicVariant, Map, PartialReflect, ReflectRef, Set, TypeRegistry,
+    VariantType,
+};
+use serde::de::{
+    DeserializeSeed, EnumAccess, Error as _, IgnoredAny, MapAccess, SeqAccess, VariantAccess,
+    Visitor,
+};
+use serde::ser::{Error as _, SerializeMap, SerializeSeq, SerializeStruct, SerializeTupleVariant};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Formatter;
+use tracing::warn;
+
+/// Serializes a [`SavedEffect`], using the type registry to serialize its data and components.
+///
+/// The effect's data is written along with its structure, rather than using the current definition of the effect type,
+/// so it can still be read and [migrated](crate::EffectMigrations) after the effect's fields have changed.
+pub struct SavedEffectSerializer<'a> {
+    /// The effect to serialize.
+    pub effect: &'a SavedEffect,
+    /// The type registry used to serialize the effect's data and components.
+    pub type_registry: &'a TypeRegistry,
+}
+
+/// Deserializes a [`SavedEffect`] that was written using [`SavedEffectSerializer`].
+pub struct SavedEffectDeserializer<'a> {
+    /// The type registry used to deserialize the effect's data and components.
+    pub type_registry: &'a TypeRegistry,
+}
+
+const FIELDS: &[&str] = &["id", "version", "target", "data", "components"];
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum Field {
+    Id,
+    Version,
+    Target,
+    Data,
+    Components,
+}
+
+impl Serialize for SavedEffectSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("SavedEffect", FIELDS.len())?;
+        state.serialize_field("id", &self.effect.id)?;
+        state.serialize_field("version", &self.effect.version)?;
+        state.serialize_field("target", &self.effect.target)?;
+        state.serialize_field(
+            "data",
+            &DataSerializer {
+                value: self.effect.data.as_ref(),
+                type_registry: self.type_registry,
+            },
+        )?;
+        state.serialize_field(
+            "components",
+            &ComponentsSerializer {
+                components: &self.effect.components,
+                type_registry: self.type_registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for SavedEffectDeserializer<'_> {
+    type Value = SavedEffect;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("SavedEffect", FIELDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for SavedEffectDeserializer<'_> {
+    type Value = SavedEffect;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a saved effect")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut id = None;
+        let mut version = None;
+        let mut target = None;
+        let mut data = None;
+        let mut components = Vec::new();
+
+        while let Some(field) = map.next_key::<Field>()? {
+            match field {
+                Field::Id => id = Some(map.next_value()?),
+                Field::Version => version = Some(map.next_value()?),
+                Field::Target => target = map.next_value()?,
+                Field::Data => {
+                    data = Some(map.next_value_seed(DataDeserializer {
+                        type_registry: self.type_registry,
+                    })?)
+                }
+                Field::Components => {
+                    components = map.next_value_seed(ComponentsDeserializer {
+                        type_registry: self.type_registry,
+                        skip_unknown: true,
+                    })?
+                }
+            }
+        }
+
+        Ok(SavedEffect {
+            id: id.ok_or_else(|| A::Error::missing_field("id"))?,
+            version: version.ok_or_else(|| A::Error::missing_field("version"))?,
+            data: data.ok_or_else(|| A::Error::missing_field("data"))?,
+            target,
+            components,
+        })
+    }
+}
+
+/// Serializes reflected components as a map of type paths to components.
+struct ComponentsSerializer<'a> {
+    components: &'a [Box<dyn PartialReflect>],
+    type_registry: &'a TypeRegistry,
+}
+
+impl Serialize for ComponentsSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_map(Some(self.components.len()))?;
+
+        for component in self.components {
+            let type_info = component.get_represented_type_info().ok_or_else(|| {
+                S::Error::custom(format!(
+                    "`{}` doesn't represent a type",
+                    component.reflect_type_path()
+                ))
+            })?;
+
+            state.serialize_entry(
+                type_info.type_path(),
+                &TypedReflectSerializer::new(component.as_ref(), self.type_registry),
+            )?;
+        }
+
+        state.end()
+    }
+}
+
+/// Deserializes a map of type paths to reflected components.
+pub(crate) struct ComponentsDeserializer<'a> {
+    pub(crate) type_registry: &'a TypeRegistry,
+    /// If true, components that aren't registered are skipped with a warning, instead of failing.
+    pub(crate) skip_unknown: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for ComponentsDeserializer<'_> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ComponentsDeserializer<'_> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a map of type paths to components")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut components = Vec::new();
+
+        while let Some(type_path) = map.next_key::<String>()? {
+            let Some(registration) = self.type_registry.get_with_type_path(&type_path) else {
+                if !self.skip_unknown {
+                    return Err(A::Error::custom(format!("`{type_path}` is not registered")));
+                }
+
+                warn!("Skipped saved component `{type_path}`, since it isn't registered");
+                map.next_value::<IgnoredAny>()?;
+                continue;
+            };
+
+            components.push(map.next_value_seed(TypedReflectDeserializer::new(
+                registration,
+                self.type_registry,
+            ))?);
+        }
+
+        Ok(components)
+    }
+}
+
+const DATA: &str = "Data";
+
+const KINDS: &[&str] = &[
+    "Struct",
+    "TupleStruct",
+    "Tuple",
+    "List",
+    "Array",
+    "Map",
+    "Set",
+    "UnitVariant",
+    "TupleVariant",
+    "StructVariant",
+    "Value",
+];
+
+/// The kind of reflected data, which is written before the data itself.
+#[derive(Deserialize)]
+#[serde(variant_identifier)]
+enum Kind {
+    Struct,
+    TupleStruct,
+    Tuple,
+    List,
+    Array,
+    Map,
+    Set,
+    UnitVariant,
+    TupleVariant,
+    StructVariant,
+    Value,
+}
+
+/// Serializes reflected data along with its structure, so it doesn't need to match the current type to be deserialized.
+///
+/// Only opaque values, such as numbers and strings, are written using their type path.
+struct DataSerializer<'a> {
+    value: &'a dyn PartialReflect,
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a> DataSerializer<'a> {
+    fn fields(
+        &self,
+        fields: impl Iterator<Item = (&'a str, &'a dyn PartialReflect)>,
+    ) -> Fields<'a> {
+        Fields {
+            fields: fields.collect(),
+            type_registry: self.type_registry,
+        }
+    }
+
+    fn items(&self, items: impl Iterator<Item = &'a dyn PartialReflect>) -> Items<'a> {
+        Items {
+            items: items.collect(),
+            type_registry: self.type_registry,
+        }
+    }
+}
+
+impl Serialize for DataSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.value.reflect_ref() {
+            ReflectRef::Struct(value) => serializer.serialize_newtype_variant(
+                DATA,
+                0,
+                "Struct",
+                &self.fields(
+                    (0..value.field_len())
+                        .filter_map(|i| Some((value.name_at(i)?, value.field_at(i)?))),
+                ),
+            ),
+            ReflectRef::TupleStruct(value) => serializer.serialize_newtype_variant(
+                DATA,
+                1,
+                "TupleStruct",
+                &self.items(value.iter_fields()),
+            ),
+            ReflectRef::Tuple(value) => serializer.serialize_newtype_variant(
+                DATA,
+                2,
+                "Tuple",
+                &self.items(value.iter_fields()),
+            ),
+            ReflectRef::List(value) => {
+                serializer.serialize_newtype_variant(DATA, 3, "List", &self.items(value.iter()))
+            }
+            ReflectRef::Array(value) => {
+                serializer.serialize_newtype_variant(DATA, 4, "Array", &self.items(value.iter()))
+            }
+            ReflectRef::Map(value) => serializer.serialize_newtype_variant(
+                DATA,
+                5,
+                "Map",
+                &Entries {
+                    entries: value.iter().collect(),
+                    type_registry: self.type_registry,
+                },
+            ),
+            ReflectRef::Set(value) => {
+                serializer.serialize_newtype_variant(DATA, 6, "Set", &self.items(value.iter()))
+            }
+            ReflectRef::Enum(value) => match value.variant_type() {
+                VariantType::Unit => serializer.serialize_newtype_variant(
+                    DATA,
+                    7,
+                    "UnitVariant",
+                    value.variant_name(),
+                ),
+                VariantType::Tuple => {
+                    let mut state =
+                        serializer.serialize_tuple_variant(DATA, 8, "TupleVariant", 2)?;
+                    state.serialize_field(value.variant_name())?;
+                    state.serialize_field(
+                        &self.items(value.iter_fields().map(|field| field.value())),
+                    )?;
+                    state.end()
+                }
+                VariantType::Struct => {
+                    let mut state =
+                        serializer.serialize_tuple_variant(DATA, 9, "StructVariant", 2)?;
+                    state.serialize_field(value.variant_name())?;
+                    state.serialize_field(
+                        &self.fields(
+                            value
+                                .iter_fields()
+                                .filter_map(|field| Some((field.name()?, field.value()))),
+                        ),
+                    )?;
+                    state.end()
+                }
+            },
+            _ => serializer.serialize_newtype_variant(
+                DATA,
+                10,
+                "Value",
+                &ReflectSerializer::new(self.value, self.type_registry),
+            ),
+        }
+    }
+}
+
+/// Serializes named fields as a map.
+struct Fields<'a> {
+    fields: Vec<(&'a str, &'a dyn PartialReflect)>,
+    type_registry: &'a TypeRegistry,
+}
+
+impl Serialize for Fields<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_map(Some(self.fields.len()))?;
+
+        for (name, value) in &self.fields {
+            state.serialize_entry(
+                name,
+                &DataSerializer {
+                    value: *value,
+                    type_registry: self.type_registry,
+                },
+            )?;
+        }
+
+        state.end()
+    }
+}
+
+/// Serializes unnamed fields or items as a sequence.
+struct Items<'a> {
+    items: Vec<&'a dyn PartialReflect>,
+    type_registry: &'a TypeRegistry,
+}
+
+impl Serialize for Items<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_seq(Some(self.items.len()))?;
+
+        for value in &self.items {
+            state.serialize_element(&DataSerializer {
+                value: *value,
+                type_registry: self.type_registry,
+            })?;
+        }
+
+        state.end()
+    }
+}
+
+/// Serializes map entries as a sequence of key-value pairs.
+struct Entries<'a> {
+    entries: Vec<(&'a dyn PartialReflect, &'a dyn PartialReflect)>,
+    type_registry: &'a TypeRegistry,
+}
+
+impl Serialize for Entries<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_seq(Some(self.entries.len()))?;
+
+        for (key, value) in &self.entries {
+            let data = |value| DataSerializer {
+                value,
+                type_registry: self.type_registry,
+            };
+            state.serialize_element(&(data(*key), data(*value)))?;
+        }
+
+        state.end()
+    }
+}
+
+/// Deserializes data written by [`DataSerializer`] into dynamic types.
+#[derive(Copy, Clone)]
+struct DataDeserializer<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for DataDeserializer<'_> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_enum(DATA, KINDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for DataDeserializer<'_> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("reflected effect data")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        let (kind, variant) = data.variant::<Kind>()?;
+
+        Ok(match kind {
+            Kind::Struct => {
+                let mut value = DynamicStruct::default();
+                for (name, field) in variant.newtype_variant_seed(FieldsDeserializer(self))? {
+                    value.insert_boxed(name, field);
+                }
+                Box::new(value)
+            }
+            Kind::TupleStruct => {
+                let mut value = DynamicTupleStruct::default();
+                for field in variant.newtype_variant_seed(ItemsDeserializer(self))? {
+                    value.insert_boxed(field);
+                }
+                Box::new(value)
+            }
+            Kind::Tuple => {
+                let mut value = DynamicTuple::default();
+                for field in variant.newtype_variant_seed(ItemsDeserializer(self))? {
+                    value.insert_boxed(field);
+                }
+                Box::new(value)
+            }
+            Kind::List => {
+                let mut value = DynamicList::default();
+                for item in variant.newtype_variant_seed(ItemsDeserializer(self))? {
+                    value.push_box(item);
+                }
+                Box::new(value)
+            }
+            Kind::Array => Box::new(DynamicArray::new(
+                variant
+                    .newtype_variant_seed(ItemsDeserializer(self))?
+                    .into_boxed_slice(),
+            )),
+            Kind::Map => {
+                let mut value = DynamicMap::default();
+                for (key, entry) in variant.newtype_variant_seed(EntriesDeserializer(self))? {
+                    value.insert_boxed(key, entry);
+                }
+                Box::new(value)
+            }
+            Kind::Set => {
+                let mut value = DynamicSet::default();
+                for item in variant.newtype_variant_seed(ItemsDeserializer(self))? {
+                    value.insert_boxed(item);
+                }
+                Box::new(value)
+            }
+            Kind::UnitVariant => Box::new(DynamicEnum::new(
+                variant.newtype_variant::<String>()?,
+                DynamicVariant::Unit,
+            )),
+            Kind::TupleVariant => variant.tuple_variant(2, VariantDeserializer::Tuple(self))?,
+            Kind::StructVariant => variant.tuple_variant(2, VariantDeserializer::Struct(self))?,
+            Kind::Value => {
+                variant.newtype_variant_seed(ReflectDeserializer::new(self.type_registry))?
+            }
+        })
+    }
+}
+
+/// Deserializes the name and fields of an enum variant.
+enum VariantDeserializer<'a> {
+    Tuple(DataDeserializer<'a>),
+    Struct(DataDeserializer<'a>),
+}
+
+impl<'de> Visitor<'de> for VariantDeserializer<'_> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an enum variant name and its fields")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let name: String = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+
+        let variant = match self {
+            Self::Tuple(data) => {
+                let mut tuple = DynamicTuple::default();
+                for field in seq
+                    .next_element_seed(ItemsDeserializer(data))?
+                    .ok_or_else(|| A::Error::invalid_length(1, &"a tuple variant"))?
+                {
+                    tuple.insert_boxed(field);
+                }
+                DynamicVariant::Tuple(tuple)
+            }
+            Self::Struct(data) => {
+                let mut fields = DynamicStruct::default();
+                for (name, field) in seq
+                    .next_element_seed(FieldsDeserializer(data))?
+                    .ok_or_else(|| A::Error::invalid_length(1, &"a struct variant"))?
+                {
+                    fields.insert_boxed(name, field);
+                }
+                DynamicVariant::Struct(fields)
+            }
+        };
+
+        Ok(Box::new(DynamicEnum::new(name, variant)))
+    }
+}
+
+/// Deserializes named fields written by [`Fields`].
+struct FieldsDeserializer<'a>(DataDeserializer<'a>);
+
+impl<'de> DeserializeSeed<'de> for FieldsDeserializer<'_> {
+    type Value = Vec<(String, Box<dyn PartialReflect>)>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldsDeserializer<'_> {
+    type Value = Vec<(String, Box<dyn PartialReflect>)>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a map of field names to data")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut fields = Vec::new();
+
+        while let Some(name) = map.next_key::<String>()? {
+            fields.push((name, map.next_value_seed(self.0)?));
+        }
+
+        Ok(fields)
+    }
+}
+
+/// Deserializes unnamed fields or items written by [`Items`].
+struct ItemsDeserializer<'a>(DataDeserializer<'a>);
+
+impl<'de> DeserializeSeed<'de> for ItemsDeserializer<'_> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ItemsDeserializer<'_> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence of data")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::new();
+
+        while let Some(item) = seq.next_element_seed(self.0)? {
+            items.push(item);
+        }
+
+        Ok(items)
+    }
+}
+
+/// Deserializes map entries written by [`Entries`].
+struct EntriesDeserializer<'a>(DataDeserializer<'a>);
+
+type Entry = (Box<dyn PartialReflect>, Box<dyn PartialReflect>);
+
+impl<'de> DeserializeSeed<'de> for EntriesDeserializer<'_> {
+    type Value = Vec<Entry>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for EntriesDeserializer<'_> {
+    type Value = Vec<Entry>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence of key-value pairs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut entries = Vec::new();
+
+        while let Some(entry) = seq.next_element_seed(EntryDeserializer(self.0))? {
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Deserializes a single key-value pair.
+struct EntryDeserializer<'a>(DataDeserializer<'a>);
+
+impl<'de> DeserializeSeed<'de> for EntryDeserializer<'_> {
+    type Value = Entry;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'de> Visitor<'de> for EntryDeserializer<'_> {
+    type Value = Entry;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a key-value pair")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let key = seq
+            .next_element_seed(self.0)?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let value = seq
+            .next_element_seed(self.0)?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+
+        Ok((key, value))
+    }
+}
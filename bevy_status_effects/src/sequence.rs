@@ -0,0 +1,181 @@
+use crate::EffectClock;
+use crate::relation::EffectedBy;
+use crate::{ReflectComponent, ReflectDefault};
+use bevy_ecs::component::HookContext;
+use bevy_ecs::prelude::*;
+use bevy_ecs::world::DeferredWorld;
+use bevy_reflect::Reflect;
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy_time::{Fixed, Real, Time, Virtual};
+use std::cmp::Ordering;
+use std::time::Duration;
+
+/// The order that an effect was applied in, which decides which effect survives when effects
+/// [replace](crate::EffectMode::Replace) each other. The effect with the higher sequence number is kept,
+/// so an effect that is applied with a lower sequence number than an existing effect is despawned instead.
+///
+/// Required by [`Effecting`](crate::Effecting), and assigned from the [`EffectSequenceCounter`] when it is inserted.
+/// An explicit sequence number can be inserted instead, such as one received from a server,
+/// so that the same effects survive regardless of the order they were spawned in.
+#[derive(Component, Reflect, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Copy, Clone)]
+#[component(on_insert = assign_sequence_hook)]
+#[reflect(Component, PartialEq, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct EffectSequence(pub u64);
+
+impl EffectSequence {
+    /// The default value, which is replaced with the next value from the [`EffectSequenceCounter`] when inserted.
+    pub const UNASSIGNED: Self = Self(0);
+}
+
+/// Stores the next [`EffectSequence`] number, which is incremented every time an effect is applied.
+///
+/// The whole resource should be saved and restored when rolling back.
+#[derive(Resource, Reflect, Eq, PartialEq, Debug, Clone)]
+#[reflect(Resource, PartialEq, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct EffectSequenceCounter {
+    next: u64,
+}
+
+impl EffectSequenceCounter {
+    /// Returns the sequence number that will be assigned to the next effect.
+    pub fn peek(&self) -> u64 {
+        self.next
+    }
+
+    /// Returns the next sequence number, and advances the counter.
+    pub fn advance(&mut self) -> EffectSequence {
+        let sequence = EffectSequence(self.next);
+        self.next += 1;
+        sequence
+    }
+
+    /// Makes sure that the counter will only assign sequence numbers after the given one.
+    pub fn observe(&mut self, sequence: EffectSequence) {
+        self.next = self.next.max(sequence.0.saturating_add(1));
+    }
+}
+
+impl Default for EffectSequenceCounter {
+    fn default() -> Self {
+        Self { next: 1 }
+    }
+}
+
+/// Records when an effect was applied, using the elapsed [`Time`] and the effect's [`EffectSequence`].
+///
+/// Required by [`Effecting`](crate::Effecting), and set automatically when it is inserted.
+/// Effects can be sorted by when they were applied using [`effects_by_application`].
+#[derive(Component, Reflect, Eq, PartialEq, Hash, Debug, Default, Copy, Clone)]
+#[component(on_insert = assign_sequence_hook)]
+#[reflect(Component, PartialEq, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AppliedAt {
+    /// The [elapsed time](Time::elapsed) of the [`EffectClock`] when the effect was applied.
+    pub elapsed: Duration,
+    /// The effect's sequence number when it was applied.
+    pub sequence: EffectSequence,
+}
+
+impl Ord for AppliedAt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sequence
+            .cmp(&other.sequence)
+            .then(self.elapsed.cmp(&other.elapsed))
+    }
+}
+
+impl PartialOrd for AppliedAt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Returns the effects on the target, sorted from the first to the last applied.
+///
+/// [`EffectedBy`] is kept in the order that effects started effecting the target,
+/// which is usually the same, except for effects that were given an explicit [`EffectSequence`].
+pub fn effects_by_application(world: &World, target: Entity) -> Vec<Entity> {
+    let mut effects: Vec<(AppliedAt, Entity)> = world
+        .get::<EffectedBy>(target)
+        .into_iter()
+        .flatten()
+        .map(|effect| {
+            let applied_at = world.get::<AppliedAt>(*effect).copied().unwrap_or_default();
+            (applied_at, *effect)
+        })
+        .collect();
+
+    effects.sort();
+    effects.into_iter().map(|(_, effect)| effect).collect()
+}
+
+fn assign_sequence_hook(mut world: DeferredWorld, context: HookContext) {
+    assign_sequence(&mut world, context.entity);
+}
+
+/// Returns the elapsed time of the [`EffectClock`], or zero if its [`Time`] doesn't exist.
+fn clock_elapsed(world: &DeferredWorld) -> Duration {
+    let clock = world
+        .get_resource::<EffectClock>()
+        .copied()
+        .unwrap_or_default();
+
+    let elapsed = match clock {
+        EffectClock::Default => world.get_resource::<Time>().map(Time::elapsed),
+        EffectClock::Virtual => world.get_resource::<Time<Virtual>>().map(Time::elapsed),
+        EffectClock::Real => world.get_resource::<Time<Real>>().map(Time::elapsed),
+        EffectClock::Fixed => world.get_resource::<Time<Fixed>>().map(Time::elapsed),
+    };
+
+    elapsed.unwrap_or_default()
+}
+
+/// Assigns a sequence number to the entity if it is [unassigned](EffectSequence::UNASSIGNED),
+/// returning the entity's sequence number. Also sets [`AppliedAt`], if it hasn't been set yet.
+pub(crate) fn assign_sequence(world: &mut DeferredWorld, entity: Entity) -> EffectSequence {
+    let Some(sequence) = world.get::<EffectSequence>(entity).copied() else {
+        return EffectSequence::UNASSIGNED;
+    };
+
+    let Some(mut counter) = world.get_resource_mut::<EffectSequenceCounter>() else {
+        return sequence;
+    };
+
+    let sequence = if sequence == EffectSequence::UNASSIGNED {
+        let sequence = counter.advance();
+
+        if let Some(mut assigned) = world.get_mut::<EffectSequence>(entity) {
+            *assigned = sequence;
+        }
+
+        sequence
+    } else {
+        counter.observe(sequence);
+        sequence
+    };
+
+    let elapsed = clock_elapsed(world);
+
+    if let Some(mut applied_at) = world.get_mut::<AppliedAt>(entity)
+        && applied_at.sequence == EffectSequence::UNASSIGNED
+    {
+        *applied_at = AppliedAt { elapsed, sequence };
+    }
+
+    sequence
+}
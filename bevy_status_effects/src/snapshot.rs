@@ -0,0 +1,156 @@
+use crate::{ReflectComponent, ReflectDefault};
+use bevy_ecs::prelude::*;
+use bevy_ecs::world::{DeferredWorld, EntityRef};
+use bevy_reflect::Reflect;
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use std::sync::Arc;
+
+/// The entity that applied the effect, such as the caster of a spell.
+///
+/// When the effect is applied using [`apply_effect`](crate::apply_effect), a [`Snapshot`] of every
+/// [registered](register_snapshot) type is taken from the source.
+/// The source is mapped to its new entity when the effect is [loaded](crate::load_effect).
+#[derive(Component, Reflect, Eq, PartialEq, Debug, Clone)]
+#[reflect(Component, PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct EffectSource(#[entities] pub Entity);
+
+/// A copy of data from the effect's [`EffectSource`], taken when the effect was applied.
+///
+/// Unlike the source's live data, the snapshot doesn't change for the rest of the effect's lifetime.
+///
+/// Snapshots are taken by [`apply_effect`](crate::apply_effect) and every other API that applies a new effect,
+/// such as [`apply_effect_batch`](crate::apply_effect_batch) and [`spawn_effect_by_name`](crate::spawn_effect_by_name).
+/// Effects that are spawned with [`Effecting`](crate::Effecting) directly don't have snapshots,
+/// since the spawn hooks can't access the world to take them before the effect is merged.
+/// [Transferred](crate::transfer_effect) effects keep their snapshots, and [loaded](crate::load_effect) effects restore them.
+///
+/// Snapshots of reflected types aren't registered automatically, so `Snapshot<T>` needs to be registered separately.
+#[derive(Component, Reflect, Eq, PartialEq, Debug, Default, Clone)]
+#[reflect(Component)]
+pub struct Snapshot<T: Send + Sync + 'static>(pub T);
+
+/// Controls which snapshot is kept when an effect is [replaced](crate::EffectMode::Replace).
+#[derive(Reflect, Eq, PartialEq, Debug, Default, Copy, Clone)]
+#[reflect(PartialEq, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum SnapshotMergeMode {
+    /// The new effect's snapshot will be used, ignoring the old one.
+    #[default]
+    Replace,
+    /// The old effect's snapshot will be used, ignoring the new one.
+    Inherit,
+}
+
+type TakeSnapshot = Arc<dyn Fn(&mut World, Entity, Entity) + Send + Sync>;
+type MergeSnapshot = fn(&mut DeferredWorld, Entity, Entity);
+
+/// Stores the types that are snapshotted from an effect's [`EffectSource`] when it is applied.
+#[derive(Resource, Default)]
+pub struct SnapshotRegistry {
+    snapshots: Vec<(TakeSnapshot, MergeSnapshot)>,
+}
+
+impl SnapshotRegistry {
+    /// Returns the number of registered snapshot types.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns true if no snapshot types are registered.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+/// Registers a component to be copied from an effect's [`EffectSource`] into a [`Snapshot`] when it is applied.
+pub fn register_snapshot<T: Component + Clone>(world: &mut World, mode: SnapshotMergeMode) {
+    register_computed_snapshot::<T>(world, mode, |source| source.get::<T>().cloned());
+}
+
+/// Registers a value to be computed from an effect's [`EffectSource`] and stored in a [`Snapshot`] when it is applied.
+///
+/// The snapshot isn't inserted if the function returns `None`.
+pub fn register_computed_snapshot<T: Clone + Send + Sync + 'static>(
+    world: &mut World,
+    mode: SnapshotMergeMode,
+    compute: impl Fn(EntityRef) -> Option<T> + Send + Sync + 'static,
+) {
+    let take: TakeSnapshot = Arc::new(move |world: &mut World, effect, source| {
+        let Some(value) = world.get_entity(source).ok().and_then(&compute) else {
+            return;
+        };
+
+        world.entity_mut(effect).insert(Snapshot(value));
+    });
+
+    let merge: MergeSnapshot = match mode {
+        SnapshotMergeMode::Replace => |_, _, _| {},
+        SnapshotMergeMode::Inherit => inherit_snapshot::<T>,
+    };
+
+    world
+        .get_resource_or_init::<SnapshotRegistry>()
+        .snapshots
+        .push((take, merge));
+}
+
+/// Takes a snapshot of every registered type from the effect's [`EffectSource`], if it has one.
+pub(crate) fn take_snapshots(world: &mut World, effect: Entity) {
+    let Some(source) = world.get::<EffectSource>(effect).map(|source| source.0) else {
+        return;
+    };
+
+    let Some(registry) = world.get_resource::<SnapshotRegistry>() else {
+        return;
+    };
+
+    let takes: Vec<TakeSnapshot> = registry
+        .snapshots
+        .iter()
+        .map(|(take, _)| take.clone())
+        .collect();
+
+    for take in takes {
+        take(world, effect, source);
+    }
+}
+
+/// Merges the old effect's snapshots into the new effect's, according to each type's [`SnapshotMergeMode`].
+pub(crate) fn merge_snapshots(world: &mut DeferredWorld, old: Entity, new: Entity) {
+    let Some(registry) = world.get_resource::<SnapshotRegistry>() else {
+        return;
+    };
+
+    let merges: Vec<MergeSnapshot> = registry.snapshots.iter().map(|(_, merge)| *merge).collect();
+
+    for merge in merges {
+        merge(world, old, new);
+    }
+}
+
+fn inherit_snapshot<T: Clone + Send + Sync + 'static>(
+    world: &mut DeferredWorld,
+    old: Entity,
+    new: Entity,
+) {
+    let Some(snapshot) = world.get::<Snapshot<T>>(old).cloned() else {
+        return;
+    };
+
+    match world.get_mut::<Snapshot<T>>(new) {
+        Some(mut new) => *new = snapshot,
+        None => {
+            world.commands().entity(new).insert(snapshot);
+        }
+    }
+}
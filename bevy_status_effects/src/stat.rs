@@ -0,0 +1,267 @@
+use crate::relation::{EffectedBy, Effecting};
+use crate::sequence::AppliedAt;
+use crate::{ReflectComponent, StatusEffectSystems};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::entity::EntityHashSet;
+use bevy_ecs::intern::Interned;
+use bevy_ecs::prelude::*;
+use bevy_ecs::schedule::ScheduleLabel;
+use bevy_reflect::prelude::ReflectDefault;
+use bevy_reflect::{Reflect, TypePath};
+use std::any::type_name;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use tracing::debug_span;
+
+/// A stat that can be modified by effects, such as move speed or armor.
+///
+/// Implement this for a marker type that derives [`TypePath`] to declare a new stat, and add a [`StatPlugin`] for it.
+pub trait StatKey: TypePath + Send + Sync + 'static {}
+
+/// Aggregates the [`StatModifier`]s on a stat's effects into the [`StatValue`] of their targets.
+pub struct StatPlugin<S: StatKey> {
+    /// The schedule that stats are updated in. Defaults to [`PreUpdate`].
+    pub schedule: Interned<dyn ScheduleLabel>,
+    _marker: PhantomData<S>,
+}
+
+impl<S: StatKey> StatPlugin<S> {
+    /// Creates a plugin that updates the stat in the given schedule.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: StatKey> Default for StatPlugin<S> {
+    fn default() -> Self {
+        Self::new(PreUpdate)
+    }
+}
+
+impl<S: StatKey> Plugin for StatPlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.register_type::<StatModifier<S>>()
+            .register_type::<StatValue<S>>()
+            .add_systems(
+                self.schedule,
+                update_stat_values::<S>
+                    .in_set(StatusEffectSystems::UpdateStats)
+                    .after(StatusEffectSystems::TickTimers),
+            );
+    }
+}
+
+/// Modifies the [`StatValue`] of the entity that this effect is [effecting](Effecting).
+///
+/// Every effect entity is a separate stack, so [stacked](crate::EffectMode::Stack) effects each contribute their modifier.
+/// Modifiers are combined as `(base + sum of add) * product of multiply`,
+/// unless any of them [set](Self::set) the value, in which case the most recently [applied](AppliedAt) one is used.
+#[derive(Component, Reflect)]
+#[reflect(Component, Debug, Default, Clone)]
+pub struct StatModifier<S: StatKey> {
+    /// Added to the base value.
+    pub add: f32,
+    /// Multiplies the value, after all additions.
+    pub multiply: f32,
+    /// Overrides the value, ignoring all other modifiers.
+    pub set: Option<f32>,
+    #[reflect(ignore)]
+    _marker: PhantomData<S>,
+}
+
+impl<S: StatKey> StatModifier<S> {
+    /// Creates a modifier that adds to the stat.
+    pub fn add(value: f32) -> Self {
+        Self {
+            add: value,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a modifier that multiplies the stat.
+    pub fn multiply(value: f32) -> Self {
+        Self {
+            multiply: value,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a modifier that overrides the stat.
+    pub fn set(value: f32) -> Self {
+        Self {
+            set: Some(value),
+            ..Self::default()
+        }
+    }
+}
+
+impl<S: StatKey> Default for StatModifier<S> {
+    fn default() -> Self {
+        Self {
+            add: 0.0,
+            multiply: 1.0,
+            set: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: StatKey> Clone for StatModifier<S> {
+    fn clone(&self) -> Self {
+        Self {
+            add: self.add,
+            multiply: self.multiply,
+            set: self.set,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: StatKey> Debug for StatModifier<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatModifier")
+            .field("add", &self.add)
+            .field("multiply", &self.multiply)
+            .field("set", &self.set)
+            .finish()
+    }
+}
+
+/// The value of a stat on an entity, after applying the [`StatModifier`]s of the effects on it.
+///
+/// Only recomputed when the entity's effects, their modifiers, or the base value change.
+#[derive(Component, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct StatValue<S: StatKey> {
+    /// The value of the stat before any modifiers are applied.
+    pub base: f32,
+    value: f32,
+    #[reflect(ignore)]
+    _marker: PhantomData<S>,
+}
+
+impl<S: StatKey> StatValue<S> {
+    /// Creates a stat with the given base value.
+    pub fn new(base: f32) -> Self {
+        Self {
+            base,
+            value: base,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the modified value of the stat.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+}
+
+impl<S: StatKey> Clone for StatValue<S> {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base,
+            value: self.value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: StatKey> Debug for StatValue<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatValue")
+            .field("base", &self.base)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+/// Combines the modifiers with the base value, as described in [`StatModifier`].
+pub fn aggregate_modifiers<'a, S: StatKey>(
+    base: f32,
+    modifiers: impl IntoIterator<Item = (&'a StatModifier<S>, AppliedAt)>,
+) -> f32 {
+    let mut add = 0.0;
+    let mut multiply = 1.0;
+    let mut set: Option<(AppliedAt, f32)> = None;
+
+    for (modifier, applied_at) in modifiers {
+        add += modifier.add;
+        multiply *= modifier.multiply;
+
+        if let Some(value) = modifier.set
+            && set.is_none_or(|(last, _)| applied_at >= last)
+        {
+            set = Some((applied_at, value));
+        }
+    }
+
+    match set {
+        Some((_, value)) => value,
+        None => (base + add) * multiply,
+    }
+}
+
+/// Filters for targets whose base value or effects have changed.
+type ChangedTarget<S> = (
+    With<StatValue<S>>,
+    Or<(Changed<StatValue<S>>, Changed<EffectedBy>)>,
+);
+
+/// Filters for effects whose modifier or target has changed.
+type ChangedModifier<S> = (
+    With<StatModifier<S>>,
+    Or<(Changed<StatModifier<S>>, Changed<Effecting>)>,
+);
+
+/// Recomputes the [`StatValue`] of every target whose effects or modifiers have changed.
+#[allow(clippy::type_complexity)]
+fn update_stat_values<S: StatKey>(
+    mut stats: ParamSet<(
+        Query<Entity, ChangedTarget<S>>,
+        Query<(&mut StatValue<S>, Option<&EffectedBy>)>,
+    )>,
+    changed_modifiers: Query<&Effecting, ChangedModifier<S>>,
+    mut removed_modifiers: RemovedComponents<StatModifier<S>>,
+    mut removed_effected_by: RemovedComponents<EffectedBy>,
+    effects: Query<&Effecting>,
+    modifiers: Query<(&StatModifier<S>, Option<&AppliedAt>)>,
+) {
+    let _span = debug_span!("update_stat_values", stat = type_name::<S>()).entered();
+
+    let mut dirty = EntityHashSet::default();
+    dirty.extend(&stats.p0());
+    dirty.extend(changed_modifiers.iter().map(|effecting| effecting.0));
+    dirty.extend(removed_effected_by.read());
+    dirty.extend(
+        removed_modifiers
+            .read()
+            .filter_map(|effect| effects.get(effect).ok())
+            .map(|effecting| effecting.0),
+    );
+
+    let mut stats = stats.p1();
+
+    for target in dirty {
+        let Ok((mut stat, effected_by)) = stats.get_mut(target) else {
+            continue;
+        };
+
+        let value = aggregate_modifiers(
+            stat.base,
+            effected_by
+                .into_iter()
+                .flatten()
+                .filter_map(|effect| modifiers.get(*effect).ok())
+                .map(|(modifier, applied_at)| (modifier, applied_at.copied().unwrap_or_default())),
+        );
+
+        // Only written when it changes, so the target isn't recomputed again next frame.
+        // Compared by bits, since a NaN value would never equal itself.
+        if stat.value.to_bits() != value.to_bits() {
+            stat.value = value;
+        }
+    }
+}
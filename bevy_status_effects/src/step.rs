@@ -0,0 +1,236 @@
+use crate::{EffectTimer, ReflectComponent, ReflectDefault, TimerMergeMode};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{Reflect, TypePath};
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// A timer that counts in discrete steps, such as turns, as opposed to seconds.
+///
+/// All operations use integer math, so the results are identical across machines.
+#[derive(Reflect, Eq, PartialEq, Debug, Default, Clone)]
+#[reflect(PartialEq, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct StepTimer {
+    duration: u32,
+    elapsed: u32,
+    repeating: bool,
+    times_finished_this_tick: u32,
+}
+
+impl StepTimer {
+    /// Creates a timer that finishes once after the given number of steps.
+    pub fn once(duration: u32) -> Self {
+        Self {
+            duration,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a timer that finishes every time the given number of steps have passed.
+    pub fn repeating(duration: u32) -> Self {
+        Self {
+            duration,
+            repeating: true,
+            ..Self::default()
+        }
+    }
+
+    /// Advances the timer by a number of steps.
+    pub fn tick(&mut self, steps: u32) -> &Self {
+        if self.finished() && !self.repeating {
+            self.times_finished_this_tick = 0;
+            return self;
+        }
+
+        self.elapsed = self.elapsed.saturating_add(steps);
+
+        if self.elapsed < self.duration {
+            self.times_finished_this_tick = 0;
+        } else if !self.repeating {
+            self.elapsed = self.duration;
+            self.times_finished_this_tick = 1;
+        } else if let Some(times_finished) = self.elapsed.checked_div(self.duration) {
+            self.times_finished_this_tick = times_finished;
+            self.elapsed %= self.duration;
+        } else {
+            // Same as Bevy's timers, a zero duration repeating timer finishes an infinite number of times.
+            self.times_finished_this_tick = u32::MAX;
+        }
+
+        self
+    }
+
+    /// Returns true if the timer has reached its duration.
+    /// For repeating timers, this is the same as [`just_finished`](Self::just_finished).
+    pub fn finished(&self) -> bool {
+        if self.repeating {
+            self.just_finished()
+        } else {
+            self.elapsed >= self.duration
+        }
+    }
+
+    /// Returns true if the timer finished during the last [`tick`](Self::tick).
+    pub fn just_finished(&self) -> bool {
+        self.times_finished_this_tick > 0
+    }
+
+    /// Returns the number of times the timer finished during the last [`tick`](Self::tick).
+    pub fn times_finished_this_tick(&self) -> u32 {
+        self.times_finished_this_tick
+    }
+
+    /// Returns true if the timer will restart after it finishes.
+    pub fn is_repeating(&self) -> bool {
+        self.repeating
+    }
+
+    /// Returns the number of steps it takes for the timer to finish.
+    pub fn duration(&self) -> u32 {
+        self.duration
+    }
+
+    /// Returns the number of steps that have elapsed.
+    pub fn elapsed(&self) -> u32 {
+        self.elapsed
+    }
+
+    /// Sets the number of steps that have elapsed.
+    pub fn set_elapsed(&mut self, elapsed: u32) {
+        self.elapsed = elapsed;
+    }
+
+    /// Returns the number of steps remaining until the timer finishes.
+    pub fn remaining(&self) -> u32 {
+        self.duration.saturating_sub(self.elapsed)
+    }
+
+    /// Merges an existing timer (other) with this one, using the given merge mode.
+    pub fn merge(&mut self, other: &Self, mode: TimerMergeMode) {
+        match mode {
+            TimerMergeMode::Replace => {}
+            TimerMergeMode::Inherit => *self = other.clone(),
+            TimerMergeMode::Fraction => {
+                self.elapsed = if other.duration == 0 {
+                    self.duration
+                } else {
+                    // Widen to avoid overflow, the result is always less than or equal to `self.duration`.
+                    let elapsed = other.elapsed.min(other.duration) as u64;
+                    (elapsed * self.duration as u64 / other.duration as u64) as u32
+                };
+            }
+            TimerMergeMode::Max => {
+                if other.remaining() > self.remaining() {
+                    *self = other.clone()
+                }
+            }
+        }
+    }
+}
+
+/// A clock that advances [`StepLifetime`] and [`StepDelay`] timers, such as [`Turns`](crate::Turns) or [`Ticks`](crate::Ticks).
+pub trait StepClock: TypePath + Eq + Debug + Clone + Send + Sync + 'static {}
+
+/// Despawns the entity after a number of steps of the clock.
+#[derive(Component, Reflect, Eq, PartialEq, Debug, Clone)]
+#[reflect(Component, PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = ""),
+    reflect(Serialize, Deserialize)
+)]
+pub struct StepLifetime<C: StepClock> {
+    /// Tracks the elapsed steps. Once the timer is finished, the entity will be despawned.
+    pub timer: StepTimer,
+    /// Controls the merge behaviour when an effect is [replaced](super::EffectMode::Replace).
+    pub mode: TimerMergeMode,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _clock: PhantomData<C>,
+}
+
+impl<C: StepClock> EffectTimer for StepLifetime<C> {
+    type Duration = u32;
+
+    fn new(steps: u32) -> Self {
+        Self {
+            timer: StepTimer::once(steps),
+            ..Self::default()
+        }
+    }
+
+    fn with_mode(mut self, mode: TimerMergeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.timer.merge(&other.timer, self.mode);
+    }
+}
+
+impl<C: StepClock> Default for StepLifetime<C> {
+    fn default() -> Self {
+        Self {
+            timer: StepTimer::default(),
+            mode: TimerMergeMode::Max,
+            _clock: PhantomData,
+        }
+    }
+}
+
+/// Repeating step timer used for the delay between effect applications.
+#[derive(Component, Reflect, Eq, PartialEq, Debug, Clone)]
+#[reflect(Component, PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = ""),
+    reflect(Serialize, Deserialize)
+)]
+pub struct StepDelay<C: StepClock> {
+    /// Tracks the elapsed steps.
+    pub timer: StepTimer,
+    /// Controls the merge behaviour when an effect is [replaced](super::EffectMode::Replace).
+    pub mode: TimerMergeMode,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _clock: PhantomData<C>,
+}
+
+impl<C: StepClock> EffectTimer for StepDelay<C> {
+    type Duration = u32;
+
+    fn new(steps: u32) -> Self {
+        Self {
+            timer: StepTimer::repeating(steps),
+            ..Self::default()
+        }
+    }
+
+    fn with_mode(mut self, mode: TimerMergeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.timer.merge(&other.timer, self.mode);
+    }
+}
+
+impl<C: StepClock> Default for StepDelay<C> {
+    fn default() -> Self {
+        Self {
+            timer: StepTimer::default(),
+            mode: TimerMergeMode::Fraction,
+            _clock: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::ReflectDefault;
+use crate::step::{StepClock, StepDelay, StepLifetime};
+use crate::timer::despawn_batch;
+use bevy_ecs::prelude::*;
+use bevy_reflect::{Reflect, TypePath};
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy_utils::Parallel;
+use tracing::debug_span;
+
+/// An explicit simulation tick counter, which drives [`TickLifetime`] and [`TickDelay`] timers.
+///
+/// Call [`advance`](Self::advance) once per simulation step. The next time the timers are ticked,
+/// they will be advanced by the number of ticks since they were last ticked.
+/// The whole resource should be saved and restored when rolling back.
+#[derive(Resource, Reflect, Eq, PartialEq, Debug, Default, Clone)]
+#[reflect(Resource, PartialEq, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SimulationTick {
+    current: u32,
+    last_ticked: u32,
+}
+
+impl SimulationTick {
+    /// Advances the simulation by a single tick.
+    pub fn advance(&mut self) {
+        self.advance_by(1);
+    }
+
+    /// Advances the simulation by a number of ticks.
+    pub fn advance_by(&mut self, ticks: u32) {
+        self.current = self.current.wrapping_add(ticks);
+    }
+
+    /// Returns the current simulation tick.
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    /// Returns the number of ticks that timers have not yet been ticked by.
+    pub fn pending(&self) -> u32 {
+        self.current.wrapping_sub(self.last_ticked)
+    }
+}
+
+/// The clock for timers that are advanced by the [`SimulationTick`].
+#[derive(TypePath, Eq, PartialEq, Debug, Default, Copy, Clone)]
+pub struct Ticks;
+
+impl StepClock for Ticks {}
+
+/// Despawns the entity after a number of simulation ticks.
+pub type TickLifetime = StepLifetime<Ticks>;
+
+/// Repeating tick timer used for the delay between effect applications.
+pub type TickDelay = StepDelay<Ticks>;
+
+pub(super) fn tick_simulation_timers(
+    mut commands: Commands,
+    mut tick: ResMut<SimulationTick>,
+    mut lifetimes: Query<(Entity, &mut TickLifetime)>,
+    mut delays: Query<&mut TickDelay>,
+    mut finished: Local<Parallel<Vec<Entity>>>,
+) {
+    let ticks = tick.pending();
+    let _span = debug_span!("tick_simulation_timers", ticks).entered();
+
+    // Timers are still ticked when the simulation hasn't stepped, which clears the previous step's finished state.
+    if ticks > 0 {
+        tick.last_ticked = tick.current;
+    }
+
+    lifetimes.par_iter_mut().for_each(|(entity, mut lifetime)| {
+        if lifetime.timer.tick(ticks).finished() {
+            finished.borrow_local_mut().push(entity);
+        }
+    });
+
+    delays.par_iter_mut().for_each(|mut delay| {
+        delay.timer.tick(ticks);
+    });
+
+    despawn_batch(&mut commands, &mut finished);
+}
@@ -1,16 +1,26 @@
 use crate::ReflectComponent;
-use bevy_ecs::prelude::{Commands, Component, Entity, Query, Res};
+use bevy_ecs::prelude::{Commands, Component, Entity, Local, Query, Res, World};
 use bevy_reflect::Reflect;
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 use bevy_time::{Time, Timer, TimerMode};
+use bevy_utils::Parallel;
 use std::time::Duration;
+use tracing::debug_span;
 
 /// A timer which is used for status effects and includes a [`TimerMergeMode`].
 pub trait EffectTimer: Sized {
+    /// The unit that the timer's duration is measured in.
+    type Duration;
+
     /// Creates a new timer from a duration.
-    fn new(duration: Duration) -> Self;
+    fn new(duration: Self::Duration) -> Self;
 
     /// Creates a new time from a duration, in seconds.
-    fn from_seconds(seconds: f32) -> Self {
+    fn from_seconds(seconds: f32) -> Self
+    where
+        Self: EffectTimer<Duration = Duration>,
+    {
         Self::new(Duration::from_secs_f32(seconds))
     }
 
@@ -25,6 +35,11 @@ pub trait EffectTimer: Sized {
 /// Despawns the entity when the timer finishes.
 #[derive(Component, Reflect, Eq, PartialEq, Debug, Clone)]
 #[reflect(Component, PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub struct Lifetime {
     /// Tracks the elapsed time. Once the timer is finished, the entity will be despawned.
     pub timer: Timer,
@@ -33,6 +48,8 @@ pub struct Lifetime {
 }
 
 impl EffectTimer for Lifetime {
+    type Duration = Duration;
+
     fn new(duration: Duration) -> Self {
         Self {
             timer: Timer::new(duration, TimerMode::Once),
@@ -79,6 +96,11 @@ impl Default for Lifetime {
 /// Repeating timer used for the delay between effect applications.  
 #[derive(Component, Reflect, Eq, PartialEq, Debug, Clone)]
 #[reflect(Component, PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub struct Delay {
     /// Tracks the elapsed time.
     pub timer: Timer,
@@ -87,6 +109,8 @@ pub struct Delay {
 }
 
 impl EffectTimer for Delay {
+    type Duration = Duration;
+
     fn new(duration: Duration) -> Self {
         Self {
             timer: Timer::new(duration, TimerMode::Repeating),
@@ -133,6 +157,11 @@ impl Default for Delay {
 /// Controls the merge behaviour of a timer when it's effect is [replaced](super::EffectMode::Replace).
 #[derive(Reflect, Eq, PartialEq, Debug, Copy, Clone)]
 #[reflect(PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub enum TimerMergeMode {
     /// The new effect's time will be used, ignoring the old one.
     Replace,
@@ -144,22 +173,54 @@ pub enum TimerMergeMode {
     Max,
 }
 
-pub(super) fn despawn_finished_lifetimes(
+pub(super) fn despawn_finished_lifetimes<C: Default + Send + Sync + 'static>(
     mut commands: Commands,
-    time: Res<Time>,
+    time: Res<Time<C>>,
     mut query: Query<(Entity, &mut Lifetime)>,
+    mut finished: Local<Parallel<Vec<Entity>>>,
 ) {
-    for (entity, mut lifetime) in &mut query {
-        lifetime.timer.tick(time.delta());
+    let _span = debug_span!("despawn_finished_lifetimes").entered();
+
+    // Timers are ticked even when the delta is zero or they are paused, which clears their finished state.
+    let delta = time.delta();
 
-        if lifetime.timer.finished() {
-            commands.entity(entity).despawn();
+    query.par_iter_mut().for_each(|(entity, mut lifetime)| {
+        if lifetime.timer.tick(delta).finished() {
+            finished.borrow_local_mut().push(entity);
         }
-    }
+    });
+
+    despawn_batch(&mut commands, &mut finished);
 }
 
-pub(super) fn tick_delay(time: Res<Time>, mut query: Query<&mut Delay>) {
-    for mut delay in &mut query {
-        delay.timer.tick(time.delta());
+pub(super) fn tick_delay<C: Default + Send + Sync + 'static>(
+    time: Res<Time<C>>,
+    mut query: Query<&mut Delay>,
+) {
+    let _span = debug_span!("tick_delay").entered();
+
+    // Timers are ticked even when the delta is zero or they are paused, which clears their finished state.
+    let delta = time.delta();
+
+    query.par_iter_mut().for_each(|mut delay| {
+        delay.timer.tick(delta);
+    });
+}
+
+/// Despawns all entities that were collected while iterating in parallel, using a single command.
+pub(crate) fn despawn_batch(commands: &mut Commands, entities: &mut Parallel<Vec<Entity>>) {
+    let mut batch = Vec::new();
+    entities.drain_into(&mut batch);
+
+    if batch.is_empty() {
+        return;
     }
+
+    commands.queue(move |world: &mut World| {
+        for entity in batch {
+            if let Ok(entity) = world.get_entity_mut(entity) {
+                entity.despawn();
+            }
+        }
+    });
 }
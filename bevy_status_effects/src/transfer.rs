@@ -0,0 +1,140 @@
+use crate::apply::{can_apply_effect, trigger_applying_effect};
+use crate::error::StatusEffectError;
+use crate::relation::{EffectedBy, Effecting};
+use crate::sequence::{AppliedAt, EffectSequence};
+use bevy_ecs::prelude::*;
+use bevy_ecs::world::EntityRef;
+
+/// Whether an effect is moved or copied when it is transferred to a new target.
+#[derive(Eq, PartialEq, Debug, Default, Copy, Clone)]
+pub enum TransferMode {
+    /// The effect is removed from the old target.
+    #[default]
+    Move,
+    /// The effect is cloned, so both targets have a copy.
+    /// Requires all the effect's components to implement [`Clone`] or [`Reflect`](bevy_reflect::Reflect).
+    Clone,
+}
+
+/// Triggered when an effect is transferred to a new target.
+#[derive(Event, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct EffectTransferred {
+    /// The effect that was transferred.
+    pub original: Entity,
+    /// The effect that is now effecting the new target.
+    /// This is the same as the original, unless it was [cloned](TransferMode::Clone).
+    pub effect: Entity,
+    /// The entity that the effect was effecting.
+    pub from: Entity,
+    /// The entity that the effect is now effecting.
+    pub to: Entity,
+}
+
+/// Transfers an effect to a new target, returning the effect entity that is now effecting it.
+///
+/// The new target must [accept](can_apply_effect) the effect, and then [`ApplyingEffect`](crate::ApplyingEffect) is triggered on it,
+/// the same as when an effect is [applied](crate::apply_effect). The effect's [snapshots](crate::Snapshot) are kept.
+/// Any existing effect on the new target is replaced and has its timers merged,
+/// according to the effect's [`EffectMode`](crate::EffectMode).
+/// Triggers [`EffectTransferred`] once the effect has been transferred.
+///
+/// # Errors
+/// Returns [`StatusEffectError::InvalidEffect`] if the effect doesn't exist or isn't effecting anything,
+/// or why the new target [can't accept](can_apply_effect) the effect or an observer cancelled it.
+/// The effect is left unchanged if it is rejected.
+pub fn transfer_effect(
+    world: &mut World,
+    effect: Entity,
+    target: Entity,
+    mode: TransferMode,
+) -> Result<Entity, StatusEffectError> {
+    let from = world
+        .get::<Effecting>(effect)
+        .ok_or(StatusEffectError::InvalidEffect(effect))?
+        .0;
+
+    can_apply_effect(world, effect, target)?;
+
+    let new = match mode {
+        TransferMode::Move => effect,
+        TransferMode::Clone => world.entity_mut(effect).clone_and_spawn_with(|builder| {
+            builder.deny::<Effecting>();
+        }),
+    };
+
+    // Effects are refreshed when `Effecting` is inserted, which replaces any existing effects on the new target.
+    // The effect is given a new sequence number, so that it survives the replacement.
+    let result = trigger_applying_effect(
+        world,
+        new,
+        target,
+        (EffectSequence::UNASSIGNED, AppliedAt::default()),
+    );
+    world.flush();
+
+    if let Err(error) = result {
+        if new != effect {
+            world.try_despawn(new).ok();
+        }
+
+        return Err(error);
+    }
+
+    world.trigger(EffectTransferred {
+        original: effect,
+        effect: new,
+        from,
+        to: target,
+    });
+
+    Ok(new)
+}
+
+/// Transfers all the effects on an entity that match the filter to a new target,
+/// using [`transfer_effect`], and returns the transferred effects.
+///
+/// # Errors
+/// Stops at the first effect that is rejected, in which case the effects before it have already been transferred.
+pub fn transfer_effects(
+    world: &mut World,
+    from: Entity,
+    to: Entity,
+    mode: TransferMode,
+    mut filter: impl FnMut(EntityRef) -> bool,
+) -> Result<Vec<Entity>, StatusEffectError> {
+    if world.get_entity(to).is_err() {
+        return Err(StatusEffectError::InvalidTarget(to));
+    }
+
+    let effects: Vec<Entity> = world
+        .get::<EffectedBy>(from)
+        .map(|effected_by| effected_by.into_iter().copied().collect())
+        .unwrap_or_default();
+
+    let effects: Vec<Entity> = effects
+        .into_iter()
+        .filter(|effect| world.get_entity(*effect).is_ok_and(&mut filter))
+        .collect();
+
+    effects
+        .into_iter()
+        .map(|effect| transfer_effect(world, effect, to, mode))
+        .collect()
+}
+
+/// A [`Command`] that transfers an effect to a new target using [`transfer_effect`].
+pub struct TransferEffect {
+    /// The effect that is transferred.
+    pub effect: Entity,
+    /// The entity that the effect will be effecting.
+    pub target: Entity,
+    /// Whether the effect is moved or cloned.
+    pub mode: TransferMode,
+}
+
+impl Command<Result> for TransferEffect {
+    fn apply(self, world: &mut World) -> Result {
+        transfer_effect(world, self.effect, self.target, self.mode)?;
+        Ok(())
+    }
+}
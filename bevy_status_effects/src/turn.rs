@@ -0,0 +1,66 @@
+use crate::EffectedBy;
+use crate::step::{StepClock, StepDelay, StepLifetime};
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypePath;
+use tracing::debug_span;
+
+/// Advances all [`TurnLifetime`] and [`TurnDelay`] timers by one turn.
+///
+/// Triggering this globally, using [`Commands::trigger`], advances every effect.
+/// Triggering it on a target, using [`Commands::trigger_targets`],
+/// only advances the effects that are [effecting](crate::Effecting) that target.
+#[derive(Event, Eq, PartialEq, Debug, Default, Copy, Clone)]
+pub struct AdvanceTurn;
+
+/// The clock for timers that are advanced by [`AdvanceTurn`].
+#[derive(TypePath, Eq, PartialEq, Debug, Default, Copy, Clone)]
+pub struct Turns;
+
+impl StepClock for Turns {}
+
+/// Despawns the entity after a number of turns.
+pub type TurnLifetime = StepLifetime<Turns>;
+
+/// Repeating turn timer used for the delay between effect applications.
+pub type TurnDelay = StepDelay<Turns>;
+
+pub(super) fn advance_turn(
+    trigger: Trigger<AdvanceTurn>,
+    mut commands: Commands,
+    effected_by: Query<&EffectedBy>,
+    mut lifetimes: Query<(Entity, &mut TurnLifetime)>,
+    mut delays: Query<&mut TurnDelay>,
+) {
+    let target = trigger.target();
+    let _span = debug_span!("advance_turn", ?target).entered();
+
+    if target == Entity::PLACEHOLDER {
+        for (entity, mut lifetime) in &mut lifetimes {
+            if lifetime.timer.tick(1).finished() {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        for mut delay in &mut delays {
+            delay.timer.tick(1);
+        }
+
+        return;
+    }
+
+    let Ok(effects) = effected_by.get(target) else {
+        return;
+    };
+
+    let mut lifetimes = lifetimes.iter_many_mut(effects);
+    while let Some((entity, mut lifetime)) = lifetimes.fetch_next() {
+        if lifetime.timer.tick(1).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    let mut delays = delays.iter_many_mut(effects);
+    while let Some(mut delay) = delays.fetch_next() {
+        delay.timer.tick(1);
+    }
+}
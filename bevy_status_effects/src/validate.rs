@@ -0,0 +1,196 @@
+use crate::EffectMode;
+use crate::hook::effect_keys;
+use crate::id::EffectRegistry;
+use crate::index::EffectKey;
+use crate::relation::{EffectedBy, Effecting};
+use crate::sequence::EffectSequence;
+use crate::tick::TickLifetime;
+use crate::timer::Lifetime;
+use crate::turn::TurnLifetime;
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::prelude::*;
+use std::collections::HashMap;
+use thiserror::Error;
+use tracing::warn;
+
+/// Checks the invariants that the effect hooks rely on every frame, and logs any [`EffectViolation`]s that are found.
+///
+/// Validation scans every effect in the world, so the system is only added in debug builds.
+#[derive(Default)]
+pub struct EffectValidationPlugin {
+    /// If true, violations are [repaired](repair_effect_violations) once they have been reported.
+    pub repair: bool,
+}
+
+impl EffectValidationPlugin {
+    /// A builder that overwrites whether violations are repaired.
+    pub fn with_repair(mut self, repair: bool) -> Self {
+        self.repair = repair;
+        self
+    }
+}
+
+impl Plugin for EffectValidationPlugin {
+    fn build(&self, app: &mut App) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        app.insert_resource(EffectValidation {
+            repair: self.repair,
+        })
+        .init_resource::<EffectViolations>()
+        .add_systems(Last, validate_effects_system);
+    }
+}
+
+/// Controls the behaviour of the [`EffectValidationPlugin`].
+#[derive(Resource, Debug, Default, Copy, Clone)]
+pub struct EffectValidation {
+    /// If true, violations are [repaired](repair_effect_violations) once they have been reported.
+    pub repair: bool,
+}
+
+/// The violations that were found the last time effects were validated by the [`EffectValidationPlugin`].
+#[derive(Resource, Debug, Default, Clone)]
+pub struct EffectViolations(pub Vec<EffectViolation>);
+
+/// A broken invariant, found by [`validate_effects`].
+#[derive(Error, Eq, PartialEq, Debug, Clone)]
+pub enum EffectViolation {
+    /// A target has more than one [replace](EffectMode::Replace) effect with the same key.
+    #[error("{target} has {} replace effects with the key {key:?}: {effects:?}", effects.len())]
+    DuplicateReplace {
+        /// The entity that is being effected.
+        target: Entity,
+        /// The key that the effects share.
+        key: EffectKey,
+        /// The duplicate effects, in application order.
+        effects: Vec<Entity>,
+    },
+    /// An entity has a lifetime, but isn't [effecting](Effecting) anything.
+    #[error("{0} has a lifetime, but isn't effecting an entity")]
+    MissingTarget(Entity),
+    /// An effect is [effecting](Effecting) an entity that doesn't exist.
+    #[error("{effect} is effecting {target}, which doesn't exist")]
+    DanglingTarget {
+        /// The effect entity.
+        effect: Entity,
+        /// The target that doesn't exist.
+        target: Entity,
+    },
+}
+
+/// Checks every effect in the world, returning any broken invariants.
+pub fn validate_effects(world: &World) -> Vec<EffectViolation> {
+    let registry = world.get_resource::<EffectRegistry>();
+    let mut violations = Vec::new();
+
+    for entity in world.iter_entities() {
+        match entity.get::<Effecting>() {
+            Some(effecting) => {
+                if world.get_entity(effecting.0).is_err() {
+                    violations.push(EffectViolation::DanglingTarget {
+                        effect: entity.id(),
+                        target: effecting.0,
+                    });
+                }
+            }
+            None => {
+                if entity.contains::<Lifetime>()
+                    || entity.contains::<TurnLifetime>()
+                    || entity.contains::<TickLifetime>()
+                {
+                    violations.push(EffectViolation::MissingTarget(entity.id()));
+                }
+            }
+        }
+
+        let Some(effected_by) = entity.get::<EffectedBy>() else {
+            continue;
+        };
+
+        let mut replaced: HashMap<EffectKey, Vec<Entity>> = HashMap::new();
+
+        for effect in effected_by {
+            let Ok(effect) = world.get_entity(*effect) else {
+                continue;
+            };
+
+            if effect.get::<EffectMode>() != Some(&EffectMode::Replace) {
+                continue;
+            }
+
+            for key in effect_keys(registry, effect) {
+                replaced.entry(key).or_default().push(effect.id());
+            }
+        }
+
+        for (key, effects) in replaced {
+            if effects.len() > 1 {
+                violations.push(EffectViolation::DuplicateReplace {
+                    target: entity.id(),
+                    key,
+                    effects,
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+/// Repairs the violations returned by [`validate_effects`].
+///
+/// Duplicate effects are despawned, keeping the one with the highest [`EffectSequence`], which is the same effect that replacement keeps.
+/// Effects without a valid target are despawned.
+pub fn repair_effect_violations(world: &mut World, violations: &[EffectViolation]) {
+    for violation in violations {
+        match violation {
+            EffectViolation::DuplicateReplace { effects, .. } => {
+                let Some(kept) = effects.iter().copied().max_by_key(|effect| {
+                    world
+                        .get::<EffectSequence>(*effect)
+                        .copied()
+                        .unwrap_or_default()
+                }) else {
+                    continue;
+                };
+
+                for duplicate in effects.iter().filter(|effect| **effect != kept) {
+                    world.try_despawn(*duplicate).ok();
+                }
+
+                // Re-inserting the mode re-indexes the remaining effect.
+                if let Ok(mut kept) = world.get_entity_mut(kept) {
+                    kept.insert(EffectMode::Replace);
+                }
+            }
+            EffectViolation::MissingTarget(effect)
+            | EffectViolation::DanglingTarget { effect, .. } => {
+                world.try_despawn(*effect).ok();
+            }
+        }
+    }
+
+    world.flush();
+}
+
+/// Validates all effects, logging and storing any violations in [`EffectViolations`].
+/// Violations are then repaired if enabled in [`EffectValidation`].
+pub fn validate_effects_system(world: &mut World) {
+    let violations = validate_effects(world);
+
+    for violation in &violations {
+        warn!("Status effect invariant violated: {violation}");
+    }
+
+    if world
+        .get_resource::<EffectValidation>()
+        .is_some_and(|validation| validation.repair)
+    {
+        repair_effect_violations(world, &violations);
+    }
+
+    world.insert_resource(EffectViolations(violations));
+}
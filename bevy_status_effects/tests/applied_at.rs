@@ -0,0 +1,132 @@
+//! Tests for recording when effects were applied.
+
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use bevy_time::{Real, Time};
+use std::time::Duration;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Regenerating;
+
+fn world() -> World {
+    let mut world = World::new();
+    init_effect_hook::<Regenerating>(&mut world);
+    world.init_resource::<Time>();
+    world
+}
+
+#[test]
+fn recorded() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let first = world.spawn((Regenerating, Effecting(target))).id();
+    world
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs(2));
+    let second = world.spawn((Regenerating, Effecting(target))).id();
+
+    assert_eq!(
+        world.get::<AppliedAt>(first),
+        Some(&AppliedAt {
+            elapsed: Duration::ZERO,
+            sequence: EffectSequence(1),
+        })
+    );
+    assert_eq!(
+        world.get::<AppliedAt>(second),
+        Some(&AppliedAt {
+            elapsed: Duration::from_secs(2),
+            sequence: EffectSequence(2),
+        })
+    );
+}
+
+#[test]
+fn sorted() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let last = world
+        .spawn((Regenerating, Effecting(target), EffectSequence(30)))
+        .id();
+    let first = world
+        .spawn((Regenerating, Effecting(target), EffectSequence(10)))
+        .id();
+    let middle = world
+        .spawn((Regenerating, Effecting(target), EffectSequence(20)))
+        .id();
+
+    assert_eq!(
+        world
+            .get::<EffectedBy>(target)
+            .unwrap()
+            .into_iter()
+            .copied()
+            .collect::<Vec<_>>(),
+        vec![last, first, middle]
+    );
+    assert_eq!(
+        effects_by_application(&world, target),
+        vec![first, middle, last]
+    );
+}
+
+#[test]
+fn transfer_reapplies() {
+    let mut world = world();
+    let player = world.spawn_empty().id();
+    let enemy = world.spawn_empty().id();
+
+    let effect = world.spawn((Regenerating, Effecting(player))).id();
+    world
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs(5));
+    transfer_effect(&mut world, effect, enemy, TransferMode::Move).unwrap();
+
+    assert_eq!(
+        world.get::<AppliedAt>(effect),
+        Some(&AppliedAt {
+            elapsed: Duration::from_secs(5),
+            sequence: EffectSequence(2),
+        })
+    );
+}
+
+#[test]
+fn configured_clock() {
+    let mut world = world();
+    world.insert_resource(EffectClock::Real);
+    world.init_resource::<Time<Real>>();
+    let target = world.spawn_empty().id();
+
+    world
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs(2));
+    world
+        .resource_mut::<Time<Real>>()
+        .advance_by(Duration::from_secs(7));
+    let effect = world.spawn((Regenerating, Effecting(target))).id();
+
+    assert_eq!(
+        world.get::<AppliedAt>(effect).unwrap().elapsed,
+        Duration::from_secs(7)
+    );
+}
+
+#[test]
+fn missing_time() {
+    let mut world = world();
+    world.insert_resource(EffectClock::Fixed);
+    let target = world.spawn_empty().id();
+
+    let effect = world.spawn((Regenerating, Effecting(target))).id();
+
+    assert_eq!(
+        world.get::<AppliedAt>(effect),
+        Some(&AppliedAt {
+            elapsed: Duration::ZERO,
+            sequence: EffectSequence(1),
+        })
+    );
+}
@@ -0,0 +1,189 @@
+//! Tests for applying effects, and the reasons an effect can be rejected.
+
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use std::time::Duration;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Slowed;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Hasted;
+
+fn world() -> World {
+    let mut world = World::new();
+    init_effect_hook::<Slowed>(&mut world);
+    init_effect_hook::<Hasted>(&mut world);
+    world
+}
+
+#[test]
+fn accepted() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let effect = apply_effect(&mut world, target, Slowed).unwrap();
+
+    assert_eq!(world.get::<Effecting>(effect), Some(&Effecting(target)));
+}
+
+#[test]
+fn invalid_target() {
+    let mut world = world();
+    let missing = world.spawn_empty().id();
+    world.despawn(missing);
+
+    assert_eq!(
+        apply_effect(&mut world, missing, Slowed),
+        Err(StatusEffectError::InvalidTarget(missing))
+    );
+}
+
+#[test]
+fn immune() {
+    let mut world = world();
+    let slowed = EffectKey::of::<Slowed>();
+    let target = world.spawn(EffectImmunity([slowed.clone()].into())).id();
+    let entities = world.entities().len();
+
+    assert_eq!(
+        apply_effect(&mut world, target, Slowed),
+        Err(StatusEffectError::Immune {
+            target,
+            key: slowed
+        })
+    );
+    // The rejected effect is despawned.
+    assert_eq!(world.entities().len(), entities);
+
+    assert!(apply_effect(&mut world, target, Hasted).is_ok());
+}
+
+#[test]
+fn stack_limit() {
+    let mut world = world();
+    let slowed = EffectKey::of::<Slowed>();
+    let target = world.spawn(EffectStackLimit(2)).id();
+
+    apply_effect(&mut world, target, Slowed).unwrap();
+    apply_effect(&mut world, target, Slowed).unwrap();
+
+    assert_eq!(
+        apply_effect(&mut world, target, Slowed),
+        Err(StatusEffectError::StackLimit {
+            target,
+            key: slowed,
+            limit: 2
+        })
+    );
+
+    // Replacing effects don't count towards the limit.
+    assert!(apply_effect(&mut world, target, (Slowed, EffectMode::Replace)).is_ok());
+    assert!(apply_effect(&mut world, target, Hasted).is_ok());
+}
+
+#[test]
+fn cooldown() {
+    let mut world = world();
+    let slowed = EffectKey::of::<Slowed>();
+
+    let mut cooldowns = EffectCooldowns::default();
+    cooldowns.start(slowed.clone(), Duration::from_secs(3));
+    let target = world.spawn(cooldowns).id();
+
+    assert_eq!(
+        apply_effect(&mut world, target, Slowed),
+        Err(StatusEffectError::Cooldown {
+            target,
+            key: slowed.clone(),
+            remaining: Duration::from_secs(3)
+        })
+    );
+
+    world
+        .get_mut::<EffectCooldowns>(target)
+        .unwrap()
+        .0
+        .get_mut(&slowed)
+        .unwrap()
+        .tick(Duration::from_secs(3));
+
+    assert!(apply_effect(&mut world, target, Slowed).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "isn't a valid target")]
+fn command_error() {
+    let mut world = world();
+    let missing = world.spawn_empty().id();
+    world.despawn(missing);
+
+    world.commands().queue(ApplyEffect {
+        target: missing,
+        bundle: Slowed,
+    });
+    world.flush();
+}
+
+#[derive(Component)]
+struct Shield;
+
+#[test]
+fn intercept() {
+    let mut world = world();
+    let target = world.spawn(Shield).id();
+
+    // The shield blocks the next effect, and shortens effects while it is active.
+    world.add_observer(
+        |mut trigger: Trigger<ApplyingEffect>,
+         mut commands: Commands,
+         shields: Query<(), With<Shield>>,
+         mut lifetimes: Query<&mut Lifetime>| {
+            if !shields.contains(trigger.target()) {
+                return;
+            }
+
+            let effect = trigger.event().effect;
+            if let Ok(mut lifetime) = lifetimes.get_mut(effect) {
+                lifetime.timer.set_duration(Duration::from_secs(1));
+                return;
+            }
+
+            trigger.event_mut().cancel("blocked by shield");
+            commands.entity(trigger.target()).remove::<Shield>();
+        },
+    );
+
+    let shortened =
+        apply_effect(&mut world, target, (Slowed, Lifetime::from_seconds(5.0))).unwrap();
+    assert_eq!(
+        world.get::<Lifetime>(shortened).unwrap().timer.duration(),
+        Duration::from_secs(1)
+    );
+
+    let entities = world.entities().len();
+    assert_eq!(
+        apply_effect(&mut world, target, Slowed),
+        Err(StatusEffectError::Cancelled {
+            target,
+            reason: "blocked by shield".into()
+        })
+    );
+    assert_eq!(world.entities().len(), entities);
+    assert!(!world.entity(target).contains::<Shield>());
+
+    assert!(apply_effect(&mut world, target, Slowed).is_ok());
+}
+
+#[test]
+fn invalid_effect() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+    let missing = world.spawn_empty().id();
+    world.despawn(missing);
+
+    assert_eq!(
+        can_apply_effect(&world, missing, target),
+        Err(StatusEffectError::InvalidEffect(missing))
+    );
+}
@@ -0,0 +1,184 @@
+//! Tests for applying an effect to many targets at once.
+
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use std::time::Duration;
+
+#[derive(StatusEffect, Component, Debug, Eq, PartialEq, Default, Clone)]
+struct Burning;
+
+#[derive(Resource, Default)]
+struct Applied(Option<EffectBatch>);
+
+#[test]
+fn accepted_and_rejected() {
+    let mut world = World::new();
+    init_effect_hook::<Burning>(&mut world);
+
+    let first = world.spawn_empty().id();
+    let second = world.spawn_empty().id();
+    let missing = world.spawn_empty().id();
+    world.despawn(missing);
+
+    let batch = apply_effect_batch(&mut world, Burning, [first, missing, second]);
+
+    assert_eq!(batch.targets().collect::<Vec<_>>(), vec![first, second]);
+    assert_eq!(
+        batch.rejected,
+        vec![(missing, StatusEffectError::InvalidTarget(missing))]
+    );
+
+    for (target, effect) in &batch.accepted {
+        assert_eq!(world.get::<Effecting>(*effect), Some(&Effecting(*target)));
+        assert_eq!(world.get::<Burning>(*effect), Some(&Burning));
+    }
+}
+
+#[test]
+fn replace_per_target() {
+    let mut world = World::new();
+    init_effect_hook::<Burning>(&mut world);
+
+    let targets: Vec<Entity> = (0..3).map(|_| world.spawn_empty().id()).collect();
+
+    let mut old = Lifetime::from_seconds(10.0).with_mode(TimerMergeMode::Inherit);
+    old.timer.tick(Duration::from_secs(4));
+    let first = apply_effect_batch(
+        &mut world,
+        (Burning, EffectMode::Replace, old),
+        targets.clone(),
+    );
+    world.flush();
+
+    let new = Lifetime::from_seconds(10.0).with_mode(TimerMergeMode::Inherit);
+    let second = apply_effect_batch(
+        &mut world,
+        (Burning, EffectMode::Replace, new),
+        targets.clone(),
+    );
+    world.flush();
+
+    for effect in first.effects() {
+        assert!(world.get_entity(effect).is_err());
+    }
+
+    for (target, effect) in second.accepted {
+        assert_eq!(world.get::<EffectedBy>(target).unwrap().len(), 1);
+        assert_eq!(
+            world.get::<Lifetime>(effect).unwrap().timer.elapsed(),
+            Duration::from_secs(4)
+        );
+    }
+}
+
+#[test]
+fn command() {
+    let mut world = World::new();
+    init_effect_hook::<Burning>(&mut world);
+    world.init_resource::<Applied>();
+    world.add_observer(
+        |trigger: Trigger<EffectBatchApplied>, mut applied: ResMut<Applied>| {
+            applied.0 = Some(trigger.event().0.clone());
+        },
+    );
+
+    let target = world.spawn_empty().id();
+    world.commands().queue(ApplyEffectBatch {
+        bundle: Burning,
+        targets: vec![target],
+    });
+    world.flush();
+
+    let batch = world.resource::<Applied>().0.clone().unwrap();
+    assert_eq!(batch.targets().collect::<Vec<_>>(), vec![target]);
+    assert_eq!(world.get::<EffectedBy>(target).unwrap().len(), 1);
+}
+
+#[test]
+fn rejected_before_spawn() {
+    let mut world = World::new();
+    init_effect_hook::<Burning>(&mut world);
+
+    let key = EffectKey::of::<Burning>();
+    let immune = world.spawn(EffectImmunity([key.clone()].into())).id();
+    let target = world.spawn_empty().id();
+    let entities = world.entities().len();
+
+    let batch = apply_effect_batch(&mut world, Burning, [immune, target]);
+
+    assert_eq!(batch.targets().collect::<Vec<_>>(), vec![target]);
+    assert_eq!(
+        batch.rejected,
+        vec![(
+            immune,
+            StatusEffectError::Immune {
+                target: immune,
+                key
+            }
+        )]
+    );
+    // Only the accepted effect was spawned.
+    assert_eq!(world.entities().len(), entities + 1);
+}
+
+#[test]
+fn duplicate_targets() {
+    let mut world = World::new();
+    init_effect_hook::<Burning>(&mut world);
+
+    let key = EffectKey::of::<Burning>();
+    let limited = world.spawn(EffectStackLimit(2)).id();
+    let batch = apply_effect_batch(&mut world, Burning, [limited, limited, limited]);
+
+    assert_eq!(batch.accepted.len(), 2);
+    assert_eq!(
+        batch.rejected,
+        vec![(
+            limited,
+            StatusEffectError::StackLimit {
+                target: limited,
+                key,
+                limit: 2
+            }
+        )]
+    );
+    assert_eq!(world.get::<EffectedBy>(limited).unwrap().len(), 2);
+
+    // Each copy replaces the previous one, so only the last is still alive.
+    let target = world.spawn_empty().id();
+    let batch = apply_effect_batch(&mut world, (Burning, EffectMode::Replace), [target, target]);
+
+    assert_eq!(batch.accepted.len(), 1);
+    assert!(batch.rejected.is_empty());
+    assert_eq!(
+        world
+            .get::<EffectedBy>(target)
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![batch.accepted[0].1]
+    );
+}
+
+#[test]
+fn applying_effect() {
+    let mut world = World::new();
+    init_effect_hook::<Burning>(&mut world);
+
+    let cancelled = world.spawn_empty().id();
+    world
+        .entity_mut(cancelled)
+        .observe(|mut trigger: Trigger<ApplyingEffect>| {
+            trigger.event_mut().cancel("warded");
+        });
+    let target = world.spawn_empty().id();
+
+    let batch = apply_effect_batch(&mut world, Burning, [cancelled, target]);
+
+    assert_eq!(batch.targets().collect::<Vec<_>>(), vec![target]);
+    assert!(matches!(
+        batch.rejected.as_slice(),
+        [(entity, StatusEffectError::Cancelled { .. })] if *entity == cancelled
+    ));
+    assert!(world.get::<EffectedBy>(cancelled).is_none());
+}
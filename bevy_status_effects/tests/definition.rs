@@ -0,0 +1,174 @@
+//! Tests for loading effects from data-driven definitions.
+
+#![cfg(feature = "asset")]
+
+use bevy_app::{App, PreUpdate};
+use bevy_asset::{AssetPlugin, Assets};
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_reflect::Reflect;
+use bevy_reflect::prelude::ReflectDefault;
+use bevy_status_effects::*;
+use bevy_time::Time;
+use std::time::Duration;
+
+#[derive(StatusEffect, Component, Reflect, Debug, Eq, PartialEq, Default)]
+#[reflect(Component, Default)]
+struct Burning {
+    damage: u32,
+}
+
+const BURNING: &str = r#"(
+    name: "Burning",
+    mode: Replace,
+    lifetime: Some((seconds: 5.0, mode: Some(Inherit))),
+    tags: ["fire", "damage"],
+    components: {
+        "definition::Burning": (damage: 5),
+    },
+)"#;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        AssetPlugin::default(),
+        StatusEffectPlugin::default(),
+        EffectDefinitionPlugin::default().with_update_active(true),
+    ))
+    .init_resource::<Time>()
+    .register_type::<Burning>();
+
+    init_effect_hook::<Burning>(app.world_mut());
+    app
+}
+
+fn parse(app: &App, ron: &str) -> EffectDefinition {
+    let type_registry = app.world().resource::<AppTypeRegistry>().read();
+    EffectDefinition::from_ron(ron, &type_registry).unwrap()
+}
+
+#[test]
+fn parse_definition() {
+    let app = app();
+    let definition = parse(&app, BURNING);
+
+    assert_eq!(definition.name, "Burning");
+    assert_eq!(definition.mode, EffectMode::Replace);
+    assert_eq!(
+        definition.lifetime,
+        Some(TimerDefinition {
+            seconds: 5.0,
+            mode: Some(TimerMergeMode::Inherit)
+        })
+    );
+    assert_eq!(definition.delay, None);
+    assert_eq!(definition.tags, ["fire", "damage"]);
+    assert_eq!(definition.components.len(), 1);
+}
+
+#[test]
+fn spawn() {
+    let mut app = app();
+    let definition = parse(&app, BURNING);
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<EffectDefinition>>()
+        .add(definition);
+
+    let target = app.world_mut().spawn_empty().id();
+    let first = spawn_effect_definition(app.world_mut(), target, &handle, ()).unwrap();
+
+    app.world_mut().commands().queue(SpawnEffectDefinition {
+        definition: handle,
+        target,
+    });
+    app.world_mut().flush();
+
+    // The definition uses `EffectMode::Replace`, so the first effect should be replaced.
+    assert!(app.world().get_entity(first).is_err());
+
+    let effects = app.world().get::<EffectedBy>(target).unwrap();
+    let second = *effects.into_iter().next().unwrap();
+
+    let world = app.world();
+    assert_eq!(world.get::<Burning>(second), Some(&Burning { damage: 5 }));
+    assert_eq!(world.get::<EffectMode>(second), Some(&EffectMode::Replace));
+    assert!(world.get::<EffectTags>(second).unwrap().contains("fire"));
+    assert_eq!(
+        world.get::<Lifetime>(second).unwrap().mode,
+        TimerMergeMode::Inherit
+    );
+}
+
+#[test]
+fn update_active() {
+    let mut app = app();
+    let definition = parse(&app, BURNING);
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<EffectDefinition>>()
+        .add(definition);
+
+    let target = app.world_mut().spawn_empty().id();
+    let effect = spawn_effect_definition(app.world_mut(), target, &handle, ()).unwrap();
+    app.world_mut()
+        .get_mut::<Lifetime>(effect)
+        .unwrap()
+        .timer
+        .tick(Duration::from_secs(1));
+
+    let modified = parse(&app, &BURNING.replace("damage: 5", "damage: 10"));
+    app.world_mut()
+        .resource_mut::<Assets<EffectDefinition>>()
+        .insert(&handle, modified);
+
+    // Runs the systems that send asset events and updates active effects.
+    app.update();
+    app.world_mut().run_schedule(PreUpdate);
+
+    assert_eq!(
+        app.world().get::<Burning>(effect),
+        Some(&Burning { damage: 10 })
+    );
+    assert_eq!(
+        app.world().get::<Lifetime>(effect).unwrap().timer.elapsed(),
+        Duration::from_secs(1)
+    );
+}
+
+#[test]
+fn invalid_duration() {
+    let app = app();
+    let type_registry = app.world().resource::<AppTypeRegistry>().read();
+
+    for seconds in ["-1.0", "NaN", "inf"] {
+        let ron = BURNING.replace("seconds: 5.0", &format!("seconds: {seconds}"));
+        assert!(EffectDefinition::from_ron(&ron, &type_registry).is_err());
+    }
+}
+
+#[test]
+fn update_removed_timer() {
+    let mut app = app();
+    let definition = parse(&app, BURNING);
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<EffectDefinition>>()
+        .add(definition);
+
+    let target = app.world_mut().spawn_empty().id();
+    let effect = spawn_effect_definition(app.world_mut(), target, &handle, ()).unwrap();
+
+    let modified = parse(
+        &app,
+        &BURNING.replace("lifetime: Some((seconds: 5.0, mode: Some(Inherit))),", ""),
+    );
+    app.world_mut()
+        .resource_mut::<Assets<EffectDefinition>>()
+        .insert(&handle, modified);
+
+    app.update();
+    app.world_mut().run_schedule(PreUpdate);
+
+    assert!(app.world().get::<Lifetime>(effect).is_none());
+}
@@ -0,0 +1,116 @@
+//! Tests for the effect diagnostics.
+
+#![cfg(feature = "bevy_diagnostic")]
+
+use bevy_app::App;
+use bevy_diagnostic::{DiagnosticPath, DiagnosticsStore};
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use bevy_time::Time;
+
+#[derive(StatusEffect, Component, Default)]
+#[status_effect(id = "burning")]
+struct Burning;
+
+#[derive(StatusEffect, Component, Default)]
+struct Frozen;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((StatusEffectPlugin::default(), StatusEffectDiagnosticsPlugin))
+        .init_resource::<Time>();
+    init_effect_hook::<Burning>(app.world_mut());
+    init_effect_hook::<Frozen>(app.world_mut());
+    app
+}
+
+fn value(app: &App, path: &DiagnosticPath) -> Option<f64> {
+    app.world()
+        .resource::<DiagnosticsStore>()
+        .get(path)?
+        .value()
+}
+
+#[test]
+fn counts() {
+    let mut app = app();
+    let target = app.world_mut().spawn_empty().id();
+
+    app.world_mut().spawn((Effecting(target), Burning));
+    app.world_mut().spawn((Effecting(target), Burning));
+    let frozen = app.world_mut().spawn((Effecting(target), Frozen)).id();
+    app.update();
+
+    assert_eq!(
+        value(&app, &StatusEffectDiagnosticsPlugin::ACTIVE),
+        Some(3.0)
+    );
+    assert_eq!(
+        value(&app, &StatusEffectDiagnosticsPlugin::APPLIED),
+        Some(3.0)
+    );
+    assert_eq!(
+        value(&app, &StatusEffectDiagnosticsPlugin::REMOVED),
+        Some(0.0)
+    );
+    assert_eq!(
+        value(
+            &app,
+            &StatusEffectDiagnosticsPlugin::type_count_path("burning")
+        ),
+        Some(2.0)
+    );
+    assert_eq!(
+        value(
+            &app,
+            &StatusEffectDiagnosticsPlugin::type_count_path(std::any::type_name::<Frozen>())
+        ),
+        Some(1.0)
+    );
+
+    app.world_mut().despawn(frozen);
+    app.update();
+
+    assert_eq!(
+        value(&app, &StatusEffectDiagnosticsPlugin::ACTIVE),
+        Some(2.0)
+    );
+    assert_eq!(
+        value(&app, &StatusEffectDiagnosticsPlugin::APPLIED),
+        Some(0.0)
+    );
+    assert_eq!(
+        value(&app, &StatusEffectDiagnosticsPlugin::REMOVED),
+        Some(1.0)
+    );
+}
+
+#[test]
+fn dynamic_counts() {
+    let mut app = app();
+    register_dynamic_effect(app.world_mut(), "curse", DynamicEffectInfo::default()).unwrap();
+    let target = app.world_mut().spawn_empty().id();
+
+    spawn_dynamic_effect(app.world_mut(), target, &EffectId::new("curse"), None, ()).unwrap();
+    app.update();
+
+    assert_eq!(
+        value(
+            &app,
+            &StatusEffectDiagnosticsPlugin::type_count_path("curse")
+        ),
+        Some(1.0)
+    );
+}
+
+#[test]
+fn sanitize_type_count_path() {
+    assert_eq!(
+        StatusEffectDiagnosticsPlugin::type_count_path("/mods//fire/").as_str(),
+        "status_effects/count/_mods__fire_"
+    );
+    assert_eq!(
+        StatusEffectDiagnosticsPlugin::type_count_path("").as_str(),
+        "status_effects/count/_"
+    );
+}
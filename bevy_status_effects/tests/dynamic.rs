@@ -0,0 +1,240 @@
+//! Tests for runtime-defined dynamic effects.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::{AppTypeRegistry, ReflectComponent};
+use bevy_status_effects::*;
+use std::time::Duration;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+#[status_effect(id = "burning")]
+struct Burning;
+
+fn world() -> World {
+    let mut world = World::new();
+    init_effect_hook::<Burning>(&mut world);
+
+    let defaults = DynamicData::from([
+        (String::from("damage"), DynamicValue::from(1)),
+        (String::from("element"), DynamicValue::from("shadow")),
+    ]);
+
+    register_dynamic_effect(
+        &mut world,
+        "curse_of_x",
+        DynamicEffectInfo {
+            mode: EffectMode::Replace,
+            defaults,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    register_dynamic_effect(&mut world, "curse_of_y", DynamicEffectInfo::default()).unwrap();
+    world
+}
+
+fn damage(world: &World, entity: Entity) -> i64 {
+    world
+        .get::<DynamicEffects>(entity)
+        .unwrap()
+        .get(&"curse_of_x".into())
+        .unwrap()
+        .get("damage")
+        .and_then(DynamicValue::as_int)
+        .unwrap()
+}
+
+#[test]
+fn spawn() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let data = DynamicData::from([(String::from("damage"), DynamicValue::from(5))]);
+
+    let effect =
+        spawn_dynamic_effect(&mut world, target, &"curse_of_x".into(), Some(&data), ()).unwrap();
+
+    let dynamic = world.get::<DynamicEffects>(effect).unwrap();
+    assert_eq!(dynamic.len(), 1);
+    assert_eq!(
+        dynamic
+            .get(&"curse_of_x".into())
+            .unwrap()
+            .get("element")
+            .and_then(DynamicValue::as_str),
+        Some("shadow")
+    );
+    assert_eq!(damage(&world, effect), 5);
+    assert_eq!(world.get::<EffectMode>(effect), Some(&EffectMode::Replace));
+    assert_eq!(effect_ids(&world, effect), [EffectId::new("curse_of_x")]);
+
+    let unknown = spawn_dynamic_effect(&mut world, target, &"curse_of_z".into(), None, ());
+    assert!(matches!(unknown, Err(ReflectEffectError::UnknownId(_))));
+}
+
+#[test]
+fn replace() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let first = spawn_dynamic_effect(
+        &mut world,
+        target,
+        &"curse_of_x".into(),
+        None,
+        Lifetime::from_seconds(2.0),
+    )
+    .unwrap();
+    let other = spawn_dynamic_effect(
+        &mut world,
+        target,
+        &"curse_of_y".into(),
+        None,
+        EffectMode::Replace,
+    )
+    .unwrap();
+    world.flush();
+
+    world
+        .get_mut::<Lifetime>(first)
+        .unwrap()
+        .timer
+        .tick(Duration::from_secs(1));
+
+    let second = spawn_dynamic_effect(
+        &mut world,
+        target,
+        &"curse_of_x".into(),
+        None,
+        Lifetime::from_seconds(0.5),
+    )
+    .unwrap();
+    world.flush();
+
+    assert!(world.get_entity(first).is_err());
+    assert!(world.get_entity(other).is_ok());
+    assert_eq!(
+        world.get::<Lifetime>(second).unwrap().timer.remaining(),
+        Duration::from_secs(1)
+    );
+}
+
+#[test]
+fn stack() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let first = spawn_dynamic_effect(
+        &mut world,
+        target,
+        &"curse_of_x".into(),
+        None,
+        EffectMode::Stack,
+    )
+    .unwrap();
+    let second = spawn_dynamic_effect(
+        &mut world,
+        target,
+        &"curse_of_x".into(),
+        None,
+        EffectMode::Stack,
+    )
+    .unwrap();
+    world.flush();
+
+    assert!(world.get_entity(first).is_ok());
+    assert!(world.get_entity(second).is_ok());
+    assert_eq!(damage(&world, second), 1);
+}
+
+#[test]
+fn collision() {
+    let mut world = world();
+
+    let compiled = register_dynamic_effect(&mut world, "burning", DynamicEffectInfo::default());
+    assert!(compiled.is_err());
+
+    let dynamic = register_dynamic_effect(&mut world, "curse_of_x", DynamicEffectInfo::default());
+    assert!(dynamic.is_err());
+}
+
+#[test]
+fn mismatched_field() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let data = DynamicData::from([(String::from("damage"), DynamicValue::from("lots"))]);
+    let result = spawn_dynamic_effect(&mut world, target, &"curse_of_x".into(), Some(&data), ());
+
+    assert!(matches!(
+        result,
+        Err(ReflectEffectError::DynamicField {
+            expected: "Int",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn multiple_kinds() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let both = world
+        .spawn((
+            Effecting(target),
+            EffectMode::Replace,
+            DynamicEffects::default()
+                .with(DynamicEffect::new("curse_of_x", DynamicData::new()))
+                .with(DynamicEffect::new("curse_of_y", DynamicData::new())),
+        ))
+        .id();
+    world.flush();
+
+    let mut ids = effect_ids(&world, both);
+    ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    assert_eq!(
+        ids,
+        [EffectId::new("curse_of_x"), EffectId::new("curse_of_y")]
+    );
+
+    // Replacing one of the kinds replaces the whole entity, the same as a compiled effect.
+    let replacement = spawn_dynamic_effect(
+        &mut world,
+        target,
+        &"curse_of_y".into(),
+        None,
+        EffectMode::Replace,
+    )
+    .unwrap();
+    world.flush();
+
+    assert!(world.get_entity(both).is_err());
+    assert!(world.get_entity(replacement).is_ok());
+}
+
+#[test]
+fn reflect() {
+    let mut world = world();
+    world.init_resource::<AppTypeRegistry>();
+    world
+        .resource::<AppTypeRegistry>()
+        .write()
+        .register::<DynamicEffects>();
+    let target = world.spawn_empty().id();
+
+    let effect = spawn_dynamic_effect(&mut world, target, &"curse_of_x".into(), None, ()).unwrap();
+
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let reflect_component = type_registry
+        .get_type_data::<ReflectComponent>(std::any::TypeId::of::<DynamicEffects>())
+        .unwrap();
+    let reflected = reflect_component
+        .reflect(world.entity(effect))
+        .unwrap()
+        .to_dynamic();
+
+    assert_eq!(
+        reflected.reflect_partial_eq(world.get::<DynamicEffects>(effect).unwrap()),
+        Some(true)
+    );
+}
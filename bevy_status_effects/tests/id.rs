@@ -0,0 +1,99 @@
+//! Tests for stable effect ids and the effect registry.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_reflect::prelude::ReflectDefault;
+use bevy_reflect::{DynamicStruct, Reflect};
+use bevy_status_effects::*;
+
+#[derive(StatusEffect, Component, Reflect, Debug, Eq, PartialEq, Default)]
+#[reflect(Component, Default)]
+#[status_effect(id = "burning")]
+struct Burning {
+    damage: u32,
+}
+
+#[derive(StatusEffect, Component, Debug, Eq, PartialEq, Default)]
+#[status_effect(id = "burning")]
+struct OnFire;
+
+#[derive(StatusEffect, Component, Debug, Eq, PartialEq, Default)]
+struct Anonymous;
+
+fn world() -> World {
+    let mut world = World::new();
+    world.init_resource::<AppTypeRegistry>();
+    world
+        .resource::<AppTypeRegistry>()
+        .write()
+        .register::<Burning>();
+    init_effect_hook::<Burning>(&mut world);
+    init_effect_hook::<Anonymous>(&mut world);
+    world
+}
+
+#[test]
+fn lookup() {
+    let world = world();
+    let registry = world.resource::<EffectRegistry>();
+
+    assert_eq!(Burning::id(), Some(EffectId::new("burning")));
+    assert_eq!(Anonymous::id(), None);
+    assert_eq!(registry.id_of::<Burning>(), Some(&EffectId::new("burning")));
+    assert_eq!(registry.id_of::<Anonymous>(), None);
+
+    let registration = registry.get(&EffectId::new("burning")).unwrap();
+    assert_eq!(registration.type_id, std::any::TypeId::of::<Burning>());
+}
+
+#[test]
+#[should_panic(expected = "effect id `burning` is used by both")]
+fn collision() {
+    let mut world = world();
+    init_effect_hook::<OnFire>(&mut world);
+    world.flush();
+}
+
+#[test]
+fn try_collision() {
+    let mut world = world();
+    assert!(matches!(
+        try_init_effect_hook::<OnFire>(&mut world),
+        Err(StatusEffectError::IdCollision(_))
+    ));
+}
+
+#[test]
+fn spawn_by_id() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let mut data = DynamicStruct::default();
+    data.insert("damage", 5u32);
+
+    let first = spawn_effect_by_id(
+        &mut world,
+        target,
+        &EffectId::new("burning"),
+        Some(&data),
+        EffectMode::Replace,
+    )
+    .unwrap();
+    let second = spawn_effect_by_id(
+        &mut world,
+        target,
+        &"burning".into(),
+        None,
+        EffectMode::Replace,
+    )
+    .unwrap();
+
+    world.flush();
+
+    assert_eq!(world.get::<Burning>(first), None);
+    assert_eq!(world.get::<Burning>(second), Some(&Burning { damage: 0 }));
+    assert_eq!(effect_ids(&world, second), [EffectId::new("burning")]);
+
+    let unknown = spawn_effect_by_id(&mut world, target, &"frozen".into(), None, ());
+    assert!(matches!(unknown, Err(ReflectEffectError::UnknownId(_))));
+}
@@ -0,0 +1,107 @@
+//! Tests for the per-target effect index.
+
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Poisoned;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Slowed;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+#[status_effect(id = "burning")]
+struct Burning;
+
+#[test]
+fn tracks_replaceable_effects() {
+    let mut world = World::new();
+    init_effect_hook::<Poisoned>(&mut world);
+    init_effect_hook::<Slowed>(&mut world);
+
+    let target = world.spawn_empty().id();
+    let poisoned = world
+        .spawn((Effecting(target), EffectMode::Replace, Poisoned))
+        .id();
+    world.spawn((Effecting(target), Slowed));
+    world.flush();
+
+    let index = world.resource::<EffectIndex>();
+    assert_eq!(index.get(target, &EffectKey::of::<Poisoned>()), &[poisoned]);
+    // Stacked effects are never replaced, so they aren't indexed.
+    assert_eq!(index.get(target, &EffectKey::of::<Slowed>()), &[]);
+
+    world.entity_mut(poisoned).remove::<Poisoned>();
+    assert!(world.resource::<EffectIndex>().is_empty());
+}
+
+#[test]
+fn replaced_effects_are_removed() {
+    let mut world = World::new();
+    init_effect_hook::<Poisoned>(&mut world);
+
+    let target = world.spawn_empty().id();
+    world.spawn((Effecting(target), EffectMode::Replace, Poisoned));
+    world.flush();
+    let second = world
+        .spawn((Effecting(target), EffectMode::Replace, Poisoned))
+        .id();
+    world.flush();
+
+    let index = world.resource::<EffectIndex>();
+    assert_eq!(index.get(target, &EffectKey::of::<Poisoned>()), &[second]);
+
+    world.despawn(target);
+    assert!(world.resource::<EffectIndex>().is_empty());
+}
+
+#[test]
+fn replace_among_stacks() {
+    let mut world = World::new();
+    init_effect_hook::<Poisoned>(&mut world);
+    init_effect_hook::<Slowed>(&mut world);
+
+    let target = world.spawn_empty().id();
+    for _ in 0..100 {
+        world.spawn((Effecting(target), Slowed));
+    }
+
+    let first = world
+        .spawn((Effecting(target), EffectMode::Replace, Poisoned))
+        .id();
+    world.flush();
+    let second = world
+        .spawn((Effecting(target), EffectMode::Replace, Poisoned))
+        .id();
+    world.flush();
+
+    assert!(world.get_entity(first).is_err());
+    assert!(world.get_entity(second).is_ok());
+    assert_eq!(world.get::<EffectedBy>(target).unwrap().len(), 101);
+}
+
+#[test]
+fn stable_keys() {
+    assert_eq!(
+        EffectKey::of::<Burning>(),
+        EffectKey::Id(EffectId::new("burning"))
+    );
+    assert_eq!(
+        EffectKey::of::<Slowed>(),
+        EffectKey::TypePath(std::any::type_name::<Slowed>().into())
+    );
+
+    // Keys don't depend on the order that components are registered in.
+    let mut world = World::new();
+    init_effect_hook::<Slowed>(&mut world);
+    init_effect_hook::<Burning>(&mut world);
+
+    let target = world.spawn_empty().id();
+    let burning = world
+        .spawn((Burning, Effecting(target), EffectMode::Replace))
+        .id();
+    world.flush();
+
+    let index = world.resource::<EffectIndex>();
+    assert_eq!(index.get(target, &EffectKey::of::<Burning>()), &[burning]);
+}
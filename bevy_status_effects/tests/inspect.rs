@@ -0,0 +1,146 @@
+//! Tests for inspecting the active effects.
+
+use bevy_app::App;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_reflect::Reflect;
+use bevy_status_effects::*;
+use bevy_time::Time;
+
+#[derive(StatusEffect, Component, Reflect, Debug, Default)]
+#[reflect(Component, Debug)]
+struct Slowed {
+    percent: u32,
+}
+
+/// Isn't registered in the type registry, so its data can't be inspected.
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Stunned;
+
+fn world() -> World {
+    let mut world = World::new();
+    world.init_resource::<AppTypeRegistry>();
+    world
+        .resource::<AppTypeRegistry>()
+        .write()
+        .register::<Slowed>();
+    init_effect_hook::<Slowed>(&mut world);
+    init_effect_hook::<Stunned>(&mut world);
+    world
+}
+
+#[test]
+fn snapshot() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let slowed = world
+        .spawn((
+            Effecting(target),
+            EffectMode::Replace,
+            Lifetime::from_seconds(2.0),
+            Slowed { percent: 50 },
+        ))
+        .id();
+    let stunned = world
+        .spawn((Effecting(target), Delay::from_seconds(1.0), Stunned))
+        .id();
+
+    let snapshot = snapshot_effects(&world);
+
+    assert_eq!(
+        snapshot,
+        EffectSnapshot {
+            targets: vec![TargetSnapshot {
+                entity: target,
+                effects: vec![
+                    EffectEntitySnapshot {
+                        entity: slowed,
+                        mode: Some(EffectMode::Replace),
+                        lifetime: Some(2.0),
+                        delay: None,
+                        turn_lifetime: None,
+                        turn_delay: None,
+                        tick_lifetime: None,
+                        tick_delay: None,
+                        components: vec![EffectComponentSnapshot {
+                            type_path: String::from("inspect::Slowed"),
+                            data: Some(String::from("Slowed { percent: 50 }")),
+                        }],
+                    },
+                    EffectEntitySnapshot {
+                        entity: stunned,
+                        mode: None,
+                        lifetime: None,
+                        delay: Some(1.0),
+                        turn_lifetime: None,
+                        turn_delay: None,
+                        tick_lifetime: None,
+                        tick_delay: None,
+                        components: vec![EffectComponentSnapshot {
+                            type_path: String::from("inspect::Stunned"),
+                            data: None,
+                        }],
+                    },
+                ],
+            }],
+        }
+    );
+
+    assert_eq!(
+        snapshot.to_string(),
+        format!(
+            "Target {target}:\n  \
+            Effect {slowed} (Replace, lifetime 2.00s)\n    \
+            Slowed {{ percent: 50 }}\n  \
+            Effect {stunned} (no mode, delay 1.00s)\n    \
+            inspect::Stunned\n"
+        )
+    );
+}
+
+#[test]
+fn snapshot_step_timers() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    world.spawn((
+        Effecting(target),
+        TurnLifetime::new(3),
+        TickDelay::new(2),
+        Slowed { percent: 10 },
+    ));
+
+    let snapshot = snapshot_effects(&world);
+    let effect = &snapshot.targets[0].effects[0];
+
+    assert_eq!(effect.turn_lifetime, Some(3));
+    assert_eq!(effect.turn_delay, None);
+    assert_eq!(effect.tick_lifetime, None);
+    assert_eq!(effect.tick_delay, Some(2));
+    assert!(
+        snapshot
+            .to_string()
+            .contains("(no mode, lifetime 3 turns, delay 2 ticks)")
+    );
+}
+
+#[test]
+fn empty() {
+    let world = world();
+    assert_eq!(snapshot_effects(&world).to_string(), "No active effects\n");
+}
+
+#[test]
+fn log_flag_resets() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::default());
+    app.init_resource::<Time>();
+    app.insert_resource(LogEffectSnapshot(true));
+
+    app.update();
+    assert_eq!(
+        app.world().resource::<LogEffectSnapshot>(),
+        &LogEffectSnapshot(false)
+    );
+}
@@ -0,0 +1,118 @@
+//! Tests for effect intensity curves.
+
+use bevy_app::{App, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::curve::{EaseFunction, FunctionCurve, Interval};
+use bevy_reflect::{FromReflect, PartialReflect};
+use bevy_status_effects::*;
+use bevy_time::Time;
+use std::time::Duration;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Slowed;
+
+#[test]
+fn curves() {
+    let linear = IntensityCurve::linear(0.8, 0.0);
+    assert_eq!(linear.sample(0.0), 0.8);
+    assert!((linear.sample(0.5) - 0.4).abs() < 1e-6);
+    assert_eq!(linear.sample(2.0), 0.0);
+
+    let steps = IntensityCurve::steps(0.0, 1.0, 4);
+    assert_eq!(steps.sample(0.3), 0.25);
+
+    let ease = IntensityCurve::ease(0.0, 1.0, EaseFunction::QuadraticIn);
+    assert_eq!(ease.sample(0.5), 0.25);
+
+    let custom = IntensityCurve::custom(FunctionCurve::new(Interval::UNIT, |t| 1.0 - t * t));
+    assert_eq!(custom.sample(0.5), 0.75);
+}
+
+#[test]
+fn updated_over_lifetime() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::default())
+        .init_resource::<Time>();
+    init_effect_hook::<Slowed>(app.world_mut());
+
+    let target = app.world_mut().spawn_empty().id();
+    let effect = app
+        .world_mut()
+        .spawn((
+            Slowed,
+            Effecting(target),
+            Lifetime::from_seconds(4.0),
+            Intensity::new(IntensityCurve::linear(0.8, 0.0)),
+        ))
+        .id();
+
+    app.world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs(1));
+    app.world_mut().run_schedule(PreUpdate);
+
+    let intensity = app
+        .world_mut()
+        .query::<EffectIntensity>()
+        .get(app.world(), effect)
+        .unwrap()
+        .get();
+    assert!((intensity - 0.6).abs() < 1e-6);
+
+    // Effects without an intensity are at full strength.
+    let plain = app.world_mut().spawn((Slowed, Effecting(target))).id();
+    let scaled = app
+        .world_mut()
+        .query::<EffectIntensity>()
+        .get(app.world(), plain)
+        .unwrap()
+        .scale(5.0);
+    assert_eq!(scaled, 5.0);
+}
+
+#[test]
+fn fraction_merge() {
+    let mut world = World::new();
+    init_effect_hook::<Slowed>(&mut world);
+    let target = world.spawn_empty().id();
+
+    let mut old = Lifetime::from_seconds(4.0);
+    old.timer.tick(Duration::from_secs(3));
+    world.spawn((
+        Slowed,
+        Effecting(target),
+        EffectMode::Replace,
+        old,
+        Intensity::new(IntensityCurve::linear(0.8, 0.0)),
+    ));
+
+    let new = world
+        .spawn((
+            Slowed,
+            Effecting(target),
+            EffectMode::Replace,
+            Lifetime::from_seconds(10.0).with_mode(TimerMergeMode::Fraction),
+            Intensity::new(IntensityCurve::linear(1.0, 0.0)),
+        ))
+        .id();
+    world.flush();
+
+    // The new effect inherits 75% of the old one's progress, so its intensity is updated immediately.
+    let intensity = world.get::<Intensity>(new).unwrap().value();
+    assert!((intensity - 0.25).abs() < 1e-6);
+}
+
+#[test]
+fn reflect() {
+    let intensity = Intensity::new(IntensityCurve::linear(0.8, 0.0));
+    let dynamic = intensity.to_dynamic();
+
+    let reflected = Intensity::from_reflect(dynamic.as_partial_reflect()).unwrap();
+    assert_eq!(reflected.value(), 0.8);
+    assert_eq!(reflected.curve.sample(0.5), intensity.curve.sample(0.5));
+
+    // Custom curves aren't reflected, so a constant curve is used instead.
+    let custom = IntensityCurve::custom(FunctionCurve::new(Interval::UNIT, |t| 1.0 - t));
+    let reflected = IntensityCurve::from_reflect(custom.to_dynamic().as_partial_reflect()).unwrap();
+    assert_eq!(reflected.sample(0.5), 1.0);
+}
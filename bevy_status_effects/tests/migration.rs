@@ -0,0 +1,432 @@
+//! Tests for saving effects and migrating them to newer versions.
+
+use bevy_ecs::entity::EntityHashMap;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_reflect::prelude::ReflectDefault;
+use bevy_reflect::{DynamicStruct, PartialReflect, Reflect};
+use bevy_status_effects::*;
+
+#[derive(StatusEffect, Component, Reflect, Debug, Eq, PartialEq, Default)]
+#[reflect(Component, Default)]
+#[status_effect(id = "burning", version = 2)]
+struct Burning {
+    damage: u32,
+    ticks: u32,
+}
+
+fn world() -> World {
+    let mut world = World::new();
+    world.init_resource::<AppTypeRegistry>();
+    world.init_resource::<EffectMigrations>();
+    {
+        let mut type_registry = world.resource::<AppTypeRegistry>().write();
+        type_registry.register::<Burning>();
+        type_registry.register::<Lifetime>();
+        type_registry.register::<EffectMode>();
+        type_registry.register::<EffectSource>();
+        type_registry.register::<DynamicEffects>();
+    }
+    init_effect_hook::<Burning>(&mut world);
+    world
+}
+
+/// Renames `dmg` to `damage`.
+fn rename_damage(data: Box<dyn PartialReflect>) -> Box<dyn PartialReflect> {
+    let old = data.reflect_ref().as_struct().unwrap();
+    let mut new = DynamicStruct::default();
+    new.insert_boxed("damage", old.field("dmg").unwrap().to_dynamic());
+    Box::new(new)
+}
+
+/// Adds `ticks`, which was previously always 3.
+fn add_ticks(data: Box<dyn PartialReflect>) -> Box<dyn PartialReflect> {
+    let mut new = data.reflect_ref().as_struct().unwrap().to_dynamic_struct();
+    new.insert("ticks", 3u32);
+    Box::new(new)
+}
+
+#[test]
+fn migrate() {
+    let mut world = world();
+    world
+        .resource_mut::<EffectMigrations>()
+        .add("burning", 0, rename_damage)
+        .add("burning", 1, add_ticks);
+
+    let target = world.spawn_empty().id();
+
+    let mut data = DynamicStruct::default();
+    data.insert("dmg", 5u32);
+
+    let effect = load_effect(
+        &mut world,
+        SavedEffect::new("burning", 0, Box::new(data)),
+        Effecting(target),
+        &mut (),
+    )
+    .unwrap();
+
+    assert_eq!(
+        world.get::<Burning>(effect),
+        Some(&Burning {
+            damage: 5,
+            ticks: 3
+        })
+    );
+}
+
+#[test]
+fn missing_migration() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let result = load_effect(
+        &mut world,
+        SavedEffect::new("burning", 1, Box::new(DynamicStruct::default())),
+        Effecting(target),
+        &mut (),
+    );
+
+    assert!(matches!(
+        result,
+        Err(LoadEffectError::MissingMigration { version: 1, .. })
+    ));
+}
+
+#[test]
+fn round_trip() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+    let effect = world
+        .spawn((
+            Burning {
+                damage: 2,
+                ticks: 4,
+            },
+            Effecting(target),
+        ))
+        .id();
+
+    let saved = save_effects(&world, effect);
+    assert_eq!(saved.len(), 1);
+    assert_eq!(saved[0].version, 2);
+
+    let report = load_effects(
+        &mut world,
+        saved.into_iter().map(|saved| (saved, Effecting(target))),
+        &mut (),
+    );
+
+    assert_eq!(
+        world.get::<Burning>(report.loaded[0]),
+        Some(&Burning {
+            damage: 2,
+            ticks: 4
+        })
+    );
+}
+
+#[test]
+fn round_trip_components() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+    let effect = world
+        .spawn((
+            Burning::default(),
+            Effecting(target),
+            EffectMode::Replace,
+            Lifetime::from_seconds(2.0),
+        ))
+        .id();
+
+    let saved = save_effects(&world, effect);
+    assert_eq!(saved[0].target, Some(target));
+    world.despawn(effect);
+
+    let new_target = world.spawn_empty().id();
+    let report = load_effects(
+        &mut world,
+        saved
+            .into_iter()
+            .map(|saved| (saved, Effecting(new_target))),
+        &mut (),
+    );
+
+    let effect = report.loaded[0];
+    assert_eq!(world.get::<Effecting>(effect), Some(&Effecting(new_target)));
+    assert_eq!(world.get::<EffectMode>(effect), Some(&EffectMode::Replace));
+    assert_eq!(
+        world.get::<Lifetime>(effect),
+        Some(&Lifetime::from_seconds(2.0))
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_old_version() {
+    use serde::de::DeserializeSeed;
+
+    let mut world = world();
+    world
+        .resource_mut::<EffectMigrations>()
+        .add("burning", 0, rename_damage)
+        .add("burning", 1, add_ticks);
+
+    let target = world.spawn_empty().id();
+
+    let mut data = DynamicStruct::default();
+    data.insert("dmg", 5u32);
+    let saved = SavedEffect::new("burning", 0, Box::new(data));
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    let ron = ron::to_string(&SavedEffectSerializer {
+        effect: &saved,
+        type_registry: &type_registry,
+    })
+    .unwrap();
+
+    let saved = SavedEffectDeserializer {
+        type_registry: &type_registry,
+    }
+    .deserialize(&mut ron::Deserializer::from_str(&ron).unwrap())
+    .unwrap();
+    drop(type_registry);
+
+    let effect = load_effect(&mut world, saved, Effecting(target), &mut ()).unwrap();
+
+    assert_eq!(
+        world.get::<Burning>(effect),
+        Some(&Burning {
+            damage: 5,
+            ticks: 3
+        })
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_components() {
+    use serde::de::DeserializeSeed;
+
+    let mut world = world();
+    let target = world.spawn_empty().id();
+    let effect = world
+        .spawn((
+            Burning {
+                damage: 1,
+                ticks: 2,
+            },
+            Effecting(target),
+            Lifetime::from_seconds(3.0),
+        ))
+        .id();
+
+    let saved = save_effects(&world, effect);
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    let ron = ron::to_string(&SavedEffectSerializer {
+        effect: &saved[0],
+        type_registry: &type_registry,
+    })
+    .unwrap();
+
+    let saved = SavedEffectDeserializer {
+        type_registry: &type_registry,
+    }
+    .deserialize(&mut ron::Deserializer::from_str(&ron).unwrap())
+    .unwrap();
+    drop(type_registry);
+
+    assert_eq!(saved.target, Some(target));
+
+    let effect = load_effect(&mut world, saved, Effecting(target), &mut ()).unwrap();
+
+    assert_eq!(
+        world.get::<Burning>(effect),
+        Some(&Burning {
+            damage: 1,
+            ticks: 2
+        })
+    );
+    assert_eq!(
+        world.get::<Lifetime>(effect),
+        Some(&Lifetime::from_seconds(3.0))
+    );
+}
+
+#[test]
+fn drop_unknown() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let report = load_effects(
+        &mut world,
+        [
+            (
+                SavedEffect::new("removed", 0, Box::new(DynamicStruct::default())),
+                Effecting(target),
+            ),
+            (
+                SavedEffect::new("burning", 2, Box::new(DynamicStruct::default())),
+                Effecting(target),
+            ),
+        ],
+        &mut (),
+    );
+
+    assert_eq!(report.loaded.len(), 1);
+    assert_eq!(report.dropped.len(), 1);
+    assert!(matches!(
+        &report.dropped[0],
+        (id, LoadEffectError::UnknownId(_)) if id.as_str() == "removed"
+    ));
+}
+
+#[test]
+fn map_entities() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+    let source = world.spawn_empty().id();
+    let effect = world
+        .spawn((Burning::default(), Effecting(target), EffectSource(source)))
+        .id();
+
+    let saved = save_effects(&world, effect);
+    world.despawn(effect);
+
+    // The source is a different entity once the save is loaded.
+    let new_target = world.spawn_empty().id();
+    let new_source = world.spawn_empty().id();
+    let mut entity_map = EntityHashMap::default();
+    entity_map.insert(source, new_source);
+
+    let report = load_effects(
+        &mut world,
+        saved
+            .into_iter()
+            .map(|saved| (saved, Effecting(new_target))),
+        &mut entity_map,
+    );
+
+    assert_eq!(
+        world.get::<EffectSource>(report.loaded[0]),
+        Some(&EffectSource(new_source))
+    );
+}
+
+#[test]
+fn skip_unknown_component() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    // A component type that no longer exists.
+    let mut saved = SavedEffect::new(
+        "burning",
+        2,
+        Box::new(Burning {
+            damage: 1,
+            ticks: 2,
+        }),
+    );
+    saved.components.push(Box::new(DynamicStruct::default()));
+    saved.components.push(Box::new(Lifetime::from_seconds(3.0)));
+
+    let effect = load_effect(&mut world, saved, Effecting(target), &mut ()).unwrap();
+
+    assert_eq!(
+        world.get::<Lifetime>(effect),
+        Some(&Lifetime::from_seconds(3.0))
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_unknown_component() {
+    use serde::de::DeserializeSeed;
+
+    let mut world = world();
+    let target = world.spawn_empty().id();
+    let effect = world
+        .spawn((
+            Burning::default(),
+            Effecting(target),
+            EffectMode::Replace,
+            Lifetime::from_seconds(3.0),
+        ))
+        .id();
+
+    let saved = save_effects(&world, effect);
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    let ron = ron::to_string(&SavedEffectSerializer {
+        effect: &saved[0],
+        type_registry: &type_registry,
+    })
+    .unwrap()
+    .replace("bevy_status_effects::EffectMode", "removed::EffectMode");
+
+    let saved = SavedEffectDeserializer {
+        type_registry: &type_registry,
+    }
+    .deserialize(&mut ron::Deserializer::from_str(&ron).unwrap())
+    .unwrap();
+
+    assert_eq!(saved.components.len(), 1);
+}
+
+#[test]
+fn round_trip_dynamic() {
+    let mut world = world();
+    register_dynamic_effect(
+        &mut world,
+        "curse",
+        DynamicEffectInfo {
+            defaults: DynamicData::from([(String::from("damage"), DynamicValue::from(1))]),
+            version: 1,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    world
+        .resource_mut::<EffectMigrations>()
+        .add("curse", 0, |data| data);
+
+    let target = world.spawn_empty().id();
+    let data = DynamicData::from([(String::from("damage"), DynamicValue::from(3))]);
+    let effect = spawn_dynamic_effect(
+        &mut world,
+        target,
+        &"curse".into(),
+        Some(&data),
+        Lifetime::from_seconds(2.0),
+    )
+    .unwrap();
+
+    let saved = save_effects(&world, effect);
+    assert_eq!(saved.len(), 1);
+    assert_eq!(saved[0].id, EffectId::new("curse"));
+    assert_eq!(saved[0].version, 1);
+    assert_eq!(saved[0].components.len(), 2);
+    world.despawn(effect);
+
+    let mut saved = saved.into_iter().next().unwrap();
+    saved.version = 0;
+    let effect = load_effect(&mut world, saved, Effecting(target), &mut ()).unwrap();
+
+    let effects = world.get::<DynamicEffects>(effect).unwrap();
+    assert_eq!(
+        effects.get(&"curse".into()).unwrap().get("damage"),
+        Some(&DynamicValue::Int(3))
+    );
+    assert_eq!(
+        world.get::<Lifetime>(effect),
+        Some(&Lifetime::from_seconds(2.0))
+    );
+}
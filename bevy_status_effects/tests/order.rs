@@ -0,0 +1,154 @@
+//! Tests that effects are replaced regardless of the order their components are inserted in.
+
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use std::time::Duration;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct MyEffect;
+
+#[derive(Copy, Clone, Debug)]
+enum Part {
+    Effect,
+    Target,
+    Mode,
+}
+
+const ORDERS: [[Part; 3]; 6] = [
+    [Part::Effect, Part::Target, Part::Mode],
+    [Part::Effect, Part::Mode, Part::Target],
+    [Part::Target, Part::Effect, Part::Mode],
+    [Part::Target, Part::Mode, Part::Effect],
+    [Part::Mode, Part::Effect, Part::Target],
+    [Part::Mode, Part::Target, Part::Effect],
+];
+
+/// Spawns an effect by inserting each of its parts separately, in the given order.
+fn spawn_in_order(world: &mut World, target: Entity, order: [Part; 3]) -> Entity {
+    let lifetime = Lifetime::from_seconds(10.0).with_mode(TimerMergeMode::Inherit);
+    let entity = world.spawn(lifetime).id();
+
+    for part in order {
+        match part {
+            Part::Effect => world.entity_mut(entity).insert(MyEffect),
+            Part::Target => world.entity_mut(entity).insert(Effecting(target)),
+            Part::Mode => world.entity_mut(entity).insert(EffectMode::Replace),
+        };
+    }
+
+    world.flush();
+    entity
+}
+
+#[test]
+fn new_effect_in_every_order() {
+    for order in ORDERS {
+        let mut world = World::new();
+        init_effect_hook::<MyEffect>(&mut world);
+
+        let target = world.spawn_empty().id();
+        let old = world
+            .spawn((
+                MyEffect,
+                Effecting(target),
+                EffectMode::Replace,
+                Lifetime::from_seconds(10.0),
+            ))
+            .id();
+        world
+            .get_mut::<Lifetime>(old)
+            .unwrap()
+            .timer
+            .tick(Duration::from_secs(7));
+
+        let new = spawn_in_order(&mut world, target, order);
+
+        assert!(world.get_entity(old).is_err(), "{order:?}");
+        assert_eq!(
+            world.get::<Lifetime>(new).unwrap().timer.elapsed(),
+            Duration::from_secs(7),
+            "{order:?}"
+        );
+        assert_eq!(
+            world.get::<EffectedBy>(target).unwrap().len(),
+            1,
+            "{order:?}"
+        );
+    }
+}
+
+#[test]
+fn old_effect_in_every_order() {
+    for order in ORDERS {
+        let mut world = World::new();
+        init_effect_hook::<MyEffect>(&mut world);
+
+        let target = world.spawn_empty().id();
+        let old = spawn_in_order(&mut world, target, order);
+        let new = world
+            .spawn((MyEffect, Effecting(target), EffectMode::Replace))
+            .id();
+        world.flush();
+
+        assert!(world.get_entity(old).is_err(), "{order:?}");
+        assert!(world.get_entity(new).is_ok(), "{order:?}");
+    }
+}
+
+#[test]
+fn change_mode() {
+    let mut world = World::new();
+    init_effect_hook::<MyEffect>(&mut world);
+
+    let target = world.spawn_empty().id();
+    let first = world.spawn((MyEffect, Effecting(target))).id();
+    let second = world.spawn((MyEffect, Effecting(target))).id();
+    world.flush();
+
+    // Both effects are stacked, so changing one to replace doesn't effect the other.
+    world.entity_mut(first).insert(EffectMode::Replace);
+    world.flush();
+    assert!(world.get_entity(second).is_ok());
+
+    world.entity_mut(second).insert(EffectMode::Replace);
+    world.flush();
+    assert!(world.get_entity(first).is_err());
+
+    // Once stacked again, the effect is no longer replaced.
+    world.entity_mut(second).insert(EffectMode::Stack);
+    let third = world
+        .spawn((MyEffect, Effecting(target), EffectMode::Replace))
+        .id();
+    world.flush();
+    assert!(world.get_entity(second).is_ok());
+    assert!(world.get_entity(third).is_ok());
+}
+
+#[test]
+fn modify_mode() {
+    let mut world = World::new();
+    init_effect_hook::<MyEffect>(&mut world);
+
+    let mode = world.register_component::<EffectMode>();
+    assert!(!world.components().get_info(mode).unwrap().mutable());
+
+    let target = world.spawn_empty().id();
+    let first = world
+        .spawn((MyEffect, Effecting(target), EffectMode::Replace))
+        .id();
+    let second = world
+        .spawn((MyEffect, Effecting(target), EffectMode::Stack))
+        .id();
+    world.flush();
+    assert!(world.get_entity(first).is_ok());
+
+    // Modifying the mode runs its hooks, the same as inserting it.
+    world
+        .modify_component(second, |mode: &mut EffectMode| {
+            *mode = EffectMode::Replace;
+        })
+        .unwrap();
+    world.flush();
+    assert!(world.get_entity(first).is_err());
+    assert!(world.get_entity(second).is_ok());
+}
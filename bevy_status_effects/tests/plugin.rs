@@ -0,0 +1,88 @@
+//! Tests for configuring the plugin's schedule and clock.
+
+use bevy_app::{App, FixedUpdate, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use bevy_time::{Fixed, Time, Virtual};
+use std::time::Duration;
+
+#[test]
+fn fixed_clock() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::new(FixedUpdate).with_clock(EffectClock::Fixed));
+    app.init_resource::<Time<Fixed>>();
+    app.init_resource::<Time<Virtual>>();
+
+    let effect = app.world_mut().spawn(Lifetime::from_seconds(1.0)).id();
+
+    // Virtual time shouldn't effect the lifetime.
+    app.world_mut()
+        .resource_mut::<Time<Virtual>>()
+        .advance_by(Duration::from_secs(2));
+    app.world_mut().run_schedule(FixedUpdate);
+    assert!(app.world().get_entity(effect).is_ok());
+
+    app.world_mut()
+        .resource_mut::<Time<Fixed>>()
+        .advance_by(Duration::from_secs(2));
+    app.world_mut().run_schedule(FixedUpdate);
+    assert!(app.world().get_entity(effect).is_err());
+}
+
+#[derive(Resource, Default)]
+struct Elapsed(Option<Duration>);
+
+#[test]
+fn system_set() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::new(FixedUpdate).with_clock(EffectClock::Fixed));
+    app.init_resource::<Time<Fixed>>();
+    app.init_resource::<Elapsed>();
+    app.add_systems(
+        FixedUpdate,
+        (|query: Query<&Delay>, mut elapsed: ResMut<Elapsed>| {
+            elapsed.0 = query.single().ok().map(|delay| delay.timer.elapsed());
+        })
+        .after(StatusEffectSystems::TickTimers),
+    );
+
+    app.world_mut().spawn(Delay::from_seconds(10.0));
+    app.world_mut()
+        .resource_mut::<Time<Fixed>>()
+        .advance_by(Duration::from_secs(1));
+    app.world_mut().run_schedule(FixedUpdate);
+
+    assert_eq!(
+        app.world().resource::<Elapsed>().0,
+        Some(Duration::from_secs(1))
+    );
+}
+
+#[test]
+fn delay_zero_delta() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::default());
+    app.init_resource::<Time>();
+
+    let effect = app.world_mut().spawn(Delay::from_seconds(1.0)).id();
+    let just_finished = |app: &App| {
+        app.world()
+            .get::<Delay>(effect)
+            .unwrap()
+            .timer
+            .just_finished()
+    };
+
+    app.world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs(1));
+    app.world_mut().run_schedule(PreUpdate);
+    assert!(just_finished(&app));
+
+    // Such as when virtual time is paused.
+    app.world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::ZERO);
+    app.world_mut().run_schedule(PreUpdate);
+    assert!(!just_finished(&app));
+}
@@ -0,0 +1,171 @@
+//! Tests for spawning effects by name, using reflection.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_reflect::prelude::ReflectDefault;
+use bevy_reflect::{DynamicStruct, Reflect};
+use bevy_status_effects::*;
+
+#[derive(StatusEffect, Component, Reflect, Debug, Eq, PartialEq, Default)]
+#[reflect(Component, Default, StatusEffect)]
+struct Slowed {
+    percent: u32,
+}
+
+/// Doesn't implement `Default`, so must be created from data.
+#[derive(StatusEffect, Component, Reflect, Debug, Eq, PartialEq)]
+#[reflect(Component, StatusEffect)]
+struct Poisoned {
+    damage: u32,
+}
+
+#[derive(Component, Reflect, Debug, Eq, PartialEq, Default)]
+#[reflect(Component, Default)]
+struct NotAnEffect;
+
+fn world() -> World {
+    let mut world = World::new();
+    world.init_resource::<AppTypeRegistry>();
+    {
+        let mut registry = world.resource::<AppTypeRegistry>().write();
+        registry.register::<Slowed>();
+        registry.register::<Poisoned>();
+        registry.register::<NotAnEffect>();
+    }
+    init_effect_hook::<Slowed>(&mut world);
+    init_effect_hook::<Poisoned>(&mut world);
+    world
+}
+
+#[test]
+fn by_name() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let mut data = DynamicStruct::default();
+    data.insert("percent", 50u32);
+
+    let full = spawn_effect_by_name(
+        &mut world,
+        target,
+        "reflect::Slowed",
+        Some(&data),
+        EffectMode::Replace,
+    )
+    .unwrap();
+    let short =
+        spawn_effect_by_name(&mut world, target, "Slowed", None, EffectMode::Replace).unwrap();
+
+    world.flush();
+
+    // The effect hook should still replace the first effect.
+    assert_eq!(world.get::<Slowed>(full), None);
+    assert_eq!(world.get::<Slowed>(short), Some(&Slowed { percent: 0 }));
+}
+
+#[test]
+fn from_reflect() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let effect = spawn_effect_by_name(
+        &mut world,
+        target,
+        "Poisoned",
+        Some(&Poisoned { damage: 3 }),
+        (),
+    )
+    .unwrap();
+    assert_eq!(world.get::<Poisoned>(effect), Some(&Poisoned { damage: 3 }));
+
+    let missing_data = spawn_effect_by_name(&mut world, target, "Poisoned", None, ());
+    assert!(matches!(
+        missing_data,
+        Err(ReflectEffectError::MissingTypeData {
+            data: "Default",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn command() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let mut data = DynamicStruct::default();
+    data.insert("percent", 25u32);
+
+    world.commands().queue(SpawnEffectByName {
+        name: "Slowed".to_string(),
+        data: Some(Box::new(data)),
+        target,
+    });
+    world.flush();
+
+    let effect = *world
+        .get::<EffectedBy>(target)
+        .unwrap()
+        .into_iter()
+        .next()
+        .unwrap();
+    assert_eq!(world.get::<Slowed>(effect), Some(&Slowed { percent: 25 }));
+}
+
+#[test]
+fn errors() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    assert!(matches!(
+        spawn_effect_by_name(&mut world, target, "NotAnEffect", None, ()),
+        Err(ReflectEffectError::NotStatusEffect(_))
+    ));
+    assert!(matches!(
+        spawn_effect_by_name(&mut world, target, "Missing", None, ()),
+        Err(ReflectEffectError::NotRegistered(_))
+    ));
+}
+
+#[test]
+fn rejected() {
+    let mut world = world();
+    let immune = world
+        .spawn(EffectImmunity([EffectKey::of::<Slowed>()].into()))
+        .id();
+    let missing = world.spawn_empty().id();
+    world.despawn(missing);
+    let entities = world.entities().len();
+
+    assert!(matches!(
+        spawn_effect_by_name(&mut world, immune, "Slowed", None, ()),
+        Err(ReflectEffectError::Rejected(
+            StatusEffectError::Immune { .. }
+        ))
+    ));
+    assert!(matches!(
+        spawn_effect_by_name(&mut world, missing, "Slowed", None, ()),
+        Err(ReflectEffectError::Rejected(
+            StatusEffectError::InvalidTarget(_)
+        ))
+    ));
+    assert_eq!(world.entities().len(), entities);
+}
+
+#[test]
+fn applying_effect() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+    world
+        .entity_mut(target)
+        .observe(|trigger: Trigger<ApplyingEffect>, mut commands: Commands| {
+            commands
+                .entity(trigger.event().effect)
+                .insert(Slowed { percent: 50 });
+        });
+
+    let effect = spawn_effect_by_name(&mut world, target, "Slowed", None, ()).unwrap();
+
+    assert_eq!(world.get::<Slowed>(effect), Some(&Slowed { percent: 50 }));
+    assert_eq!(world.get::<Effecting>(effect), Some(&Effecting(target)));
+}
@@ -0,0 +1,269 @@
+//! Tests for the Bevy Remote Protocol methods.
+
+#![cfg(feature = "bevy_remote")]
+
+use bevy_app::App;
+use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
+use bevy_reflect::prelude::ReflectDefault;
+use bevy_remote::{RemoteMethodSystemId, RemoteMethods, RemotePlugin, error_codes};
+use bevy_status_effects::*;
+use serde_json::{Value, json};
+
+#[derive(StatusEffect, Component, Reflect, Debug, Eq, PartialEq, Default)]
+#[reflect(Component, Default, StatusEffect)]
+#[status_effect(id = "slowed")]
+struct Slowed {
+    percent: u32,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        StatusEffectPlugin::default(),
+        RemotePlugin::default().with_status_effect_methods(),
+    ))
+    .register_type::<Slowed>();
+    init_effect_hook::<Slowed>(app.world_mut());
+
+    let defaults = DynamicData::from([(String::from("damage"), DynamicValue::from(1))]);
+    register_dynamic_effect(
+        app.world_mut(),
+        "curse",
+        DynamicEffectInfo {
+            mode: EffectMode::Replace,
+            defaults,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    app
+}
+
+/// Runs a request in-process, using the handler that was added to the [`RemotePlugin`].
+fn request(app: &mut App, method: &str, params: Value) -> Result<Value, i16> {
+    let Some(RemoteMethodSystemId::Instant(system)) =
+        app.world().resource::<RemoteMethods>().get(method).cloned()
+    else {
+        panic!("`{method}` is not registered");
+    };
+
+    app.world_mut()
+        .run_system_with(system, Some(params))
+        .unwrap()
+        .map_err(|error| error.code)
+}
+
+#[test]
+fn apply_and_list() {
+    let mut app = app();
+    let target = app.world_mut().spawn_empty().id();
+
+    let response = request(
+        &mut app,
+        BRP_APPLY_EFFECT_METHOD,
+        json!({
+            "target": target,
+            "effect": "Slowed",
+            "data": { "percent": 25 },
+            "mode": "Replace",
+            "lifetime": 2.0,
+        }),
+    )
+    .unwrap();
+    let BrpApplyEffectResponse { entity } = serde_json::from_value(response).unwrap();
+
+    assert_eq!(
+        app.world().get::<Slowed>(entity),
+        Some(&Slowed { percent: 25 })
+    );
+    assert_eq!(
+        app.world().get::<Effecting>(entity),
+        Some(&Effecting(target))
+    );
+
+    let response = request(
+        &mut app,
+        BRP_LIST_EFFECTS_METHOD,
+        json!({ "entity": target }),
+    )
+    .unwrap();
+    let effects: Vec<BrpEffect> = serde_json::from_value(response).unwrap();
+
+    assert_eq!(
+        effects,
+        vec![BrpEffect {
+            entity,
+            type_path: Some(String::from("remote::Slowed")),
+            id: Some(EffectId::new("slowed")),
+            mode: Some(EffectMode::Replace),
+            lifetime: Some(2.0),
+            turns: None,
+            ticks: None,
+        }]
+    );
+}
+
+#[test]
+fn apply_by_id_replaces() {
+    let mut app = app();
+    let target = app.world_mut().spawn_empty().id();
+
+    let params = json!({ "target": target, "effect": "slowed", "mode": "Replace" });
+    request(&mut app, BRP_APPLY_EFFECT_METHOD, params.clone()).unwrap();
+    request(&mut app, BRP_APPLY_EFFECT_METHOD, params).unwrap();
+    app.world_mut().flush();
+
+    let response = request(
+        &mut app,
+        BRP_LIST_EFFECTS_METHOD,
+        json!({ "entity": target }),
+    )
+    .unwrap();
+    let effects: Vec<BrpEffect> = serde_json::from_value(response).unwrap();
+    assert_eq!(effects.len(), 1);
+}
+
+#[test]
+fn apply_dynamic() {
+    let mut app = app();
+    let target = app.world_mut().spawn_empty().id();
+
+    let response = request(
+        &mut app,
+        BRP_APPLY_EFFECT_METHOD,
+        json!({ "target": target, "effect": "curse", "data": { "damage": 4 } }),
+    )
+    .unwrap();
+    let BrpApplyEffectResponse { entity } = serde_json::from_value(response).unwrap();
+
+    let effects = app.world().get::<DynamicEffects>(entity).unwrap();
+    let effect = effects.get(&EffectId::new("curse")).unwrap();
+    assert_eq!(effect.get("damage"), Some(&DynamicValue::Int(4)));
+    assert_eq!(
+        app.world().get::<EffectMode>(entity),
+        Some(&EffectMode::Replace)
+    );
+}
+
+#[test]
+fn remove() {
+    let mut app = app();
+    let target = app.world_mut().spawn_empty().id();
+
+    let slowed = request(
+        &mut app,
+        BRP_APPLY_EFFECT_METHOD,
+        json!({ "target": target, "effect": "Slowed" }),
+    )
+    .unwrap();
+    let BrpApplyEffectResponse { entity: slowed } = serde_json::from_value(slowed).unwrap();
+    let curse = request(
+        &mut app,
+        BRP_APPLY_EFFECT_METHOD,
+        json!({ "target": target, "effect": "curse" }),
+    )
+    .unwrap();
+    let BrpApplyEffectResponse { entity: curse } = serde_json::from_value(curse).unwrap();
+
+    let response = request(
+        &mut app,
+        BRP_REMOVE_EFFECTS_METHOD,
+        json!({ "target": target, "effect": "curse" }),
+    )
+    .unwrap();
+    assert_eq!(response, json!([curse]));
+    assert!(app.world().get_entity(curse).is_err());
+    assert!(app.world().get_entity(slowed).is_ok());
+
+    let response = request(
+        &mut app,
+        BRP_REMOVE_EFFECTS_METHOD,
+        json!({ "target": target }),
+    )
+    .unwrap();
+    assert_eq!(response, json!([slowed]));
+    assert!(app.world().get_entity(slowed).is_err());
+}
+
+#[test]
+fn errors() {
+    let mut app = app();
+    let target = app.world_mut().spawn_empty().id();
+    let missing = app.world_mut().spawn_empty().id();
+    app.world_mut().despawn(missing);
+
+    assert_eq!(
+        request(
+            &mut app,
+            BRP_APPLY_EFFECT_METHOD,
+            json!({ "target": target, "effect": "Unknown" }),
+        ),
+        Err(error_codes::COMPONENT_ERROR)
+    );
+    assert_eq!(
+        request(
+            &mut app,
+            BRP_APPLY_EFFECT_METHOD,
+            json!({ "target": target, "effect": "curse", "data": { "unknown": 1 } }),
+        ),
+        Err(error_codes::INVALID_PARAMS)
+    );
+    assert_eq!(
+        request(
+            &mut app,
+            BRP_APPLY_EFFECT_METHOD,
+            json!({ "target": target, "effect": "curse", "data": { "damage": "lots" } }),
+        ),
+        Err(error_codes::INVALID_PARAMS)
+    );
+    assert_eq!(
+        request(
+            &mut app,
+            BRP_LIST_EFFECTS_METHOD,
+            json!({ "entity": missing })
+        ),
+        Err(error_codes::ENTITY_NOT_FOUND)
+    );
+    assert_eq!(
+        request(&mut app, BRP_REMOVE_EFFECTS_METHOD, json!({})),
+        Err(error_codes::INVALID_PARAMS)
+    );
+}
+
+#[test]
+fn invalid_lifetime() {
+    let mut app = app();
+    let target = app.world_mut().spawn_empty().id();
+
+    assert_eq!(
+        request(
+            &mut app,
+            BRP_APPLY_EFFECT_METHOD,
+            json!({ "target": target, "effect": "Slowed", "lifetime": -1.0 }),
+        ),
+        Err(error_codes::INVALID_PARAMS)
+    );
+    assert!(app.world().get::<EffectedBy>(target).is_none());
+}
+
+#[test]
+fn apply_immune() {
+    let mut app = app();
+    let slowed = EffectKey::of::<Slowed>();
+    let target = app.world_mut().spawn(EffectImmunity([slowed].into())).id();
+
+    assert_eq!(
+        request(
+            &mut app,
+            BRP_APPLY_EFFECT_METHOD,
+            json!({ "target": target, "effect": "Slowed" }),
+        ),
+        Err(error_codes::COMPONENT_ERROR)
+    );
+    assert!(app.world().get::<EffectedBy>(target).is_none());
+
+    let mut query = app.world_mut().query::<&Slowed>();
+    assert_eq!(query.iter(app.world()).count(), 0);
+}
@@ -0,0 +1,113 @@
+//! Tests that replacement depends on the effects' sequence numbers, instead of the order they were spawned in.
+
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use std::time::Duration;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Frozen;
+
+fn world() -> World {
+    let mut world = World::new();
+    init_effect_hook::<Frozen>(&mut world);
+    world
+}
+
+#[test]
+fn assigned_in_order() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let first = world.spawn((Frozen, Effecting(target))).id();
+    let second = world.spawn((Frozen, Effecting(target))).id();
+
+    assert_eq!(world.get::<EffectSequence>(first), Some(&EffectSequence(1)));
+    assert_eq!(
+        world.get::<EffectSequence>(second),
+        Some(&EffectSequence(2))
+    );
+    assert_eq!(world.resource::<EffectSequenceCounter>().peek(), 3);
+}
+
+#[test]
+fn explicit_sequence_wins() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+
+    let mut lifetime = Lifetime::from_seconds(10.0);
+    lifetime.timer.tick(Duration::from_secs(3));
+
+    // Spawned first, but applied last.
+    let later = world
+        .commands()
+        .spawn((
+            Frozen,
+            Effecting(target),
+            EffectMode::Replace,
+            EffectSequence(20),
+            Lifetime::from_seconds(10.0).with_mode(TimerMergeMode::Inherit),
+        ))
+        .id();
+    let earlier = world
+        .commands()
+        .spawn((
+            Frozen,
+            Effecting(target),
+            EffectMode::Replace,
+            EffectSequence(10),
+            lifetime,
+        ))
+        .id();
+    world.flush();
+
+    assert!(world.get_entity(earlier).is_err());
+    assert_eq!(world.get::<EffectedBy>(target).unwrap().len(), 1);
+    assert_eq!(
+        world.get::<Lifetime>(later).unwrap().timer.elapsed(),
+        Duration::from_secs(3)
+    );
+
+    // The counter continues after the explicit sequence number.
+    assert_eq!(world.resource::<EffectSequenceCounter>().peek(), 21);
+    let newest = world
+        .spawn((Frozen, Effecting(target), EffectMode::Replace))
+        .id();
+    world.flush();
+    assert!(world.get_entity(later).is_err());
+    assert!(world.get_entity(newest).is_ok());
+}
+
+#[test]
+fn same_result_in_any_spawn_order() {
+    for reversed in [false, true] {
+        let mut world = world();
+        let target = world.spawn_empty().id();
+
+        let mut sequences = vec![EffectSequence(5), EffectSequence(6)];
+        if reversed {
+            sequences.reverse();
+        }
+
+        // Both effects are spawned in the same command flush.
+        for sequence in sequences {
+            world
+                .commands()
+                .spawn((Frozen, Effecting(target), EffectMode::Replace, sequence));
+        }
+        world.flush();
+
+        let survivor = world.get::<EffectedBy>(target).unwrap().into_iter().next();
+        assert_eq!(
+            world.get::<EffectSequence>(*survivor.unwrap()),
+            Some(&EffectSequence(6))
+        );
+    }
+}
+
+#[test]
+fn observe_max() {
+    let mut counter = EffectSequenceCounter::default();
+    counter.observe(EffectSequence(u64::MAX));
+
+    assert_eq!(counter.peek(), u64::MAX);
+}
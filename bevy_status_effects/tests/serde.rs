@@ -0,0 +1,136 @@
+//! Tests for serializing effects and round-tripping them through scenes.
+
+#![cfg(feature = "serde")]
+
+use bevy_ecs::entity::EntityHashMap;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_reflect::Reflect;
+use bevy_reflect::serde::TypedReflectDeserializer;
+use bevy_scene::serde::SceneDeserializer;
+use bevy_scene::{DynamicScene, DynamicSceneBuilder};
+use bevy_status_effects::*;
+use serde::de::DeserializeSeed;
+
+#[derive(StatusEffect, Component, Reflect, Debug, Eq, PartialEq, Default)]
+#[reflect(Component)]
+struct MyEffect(u32);
+
+fn registry() -> AppTypeRegistry {
+    let registry = AppTypeRegistry::default();
+    {
+        let mut registry = registry.write();
+        registry.register::<MyEffect>();
+        registry.register::<Effecting>();
+        registry.register::<EffectedBy>();
+        registry.register::<EffectMode>();
+        registry.register::<Lifetime>();
+        registry.register::<Delay>();
+        registry.register::<TimerMergeMode>();
+    }
+    registry
+}
+
+/// Serializes the world to a RON scene, and then deserializes it again.
+fn round_trip(world: &World, registry: &AppTypeRegistry) -> DynamicScene {
+    let scene = DynamicSceneBuilder::from_world(world)
+        .extract_entities(world.iter_entities().map(|entity| entity.id()))
+        .build();
+
+    let registry = registry.read();
+    let serialized = scene.serialize(&registry).unwrap();
+
+    let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+    SceneDeserializer {
+        type_registry: &registry,
+    }
+    .deserialize(&mut deserializer)
+    .unwrap()
+}
+
+#[test]
+fn timer() {
+    let registry = registry();
+    let lifetime = Lifetime::from_seconds(2.0).with_mode(TimerMergeMode::Fraction);
+
+    let serialized = ron::to_string(&lifetime).unwrap();
+    let deserialized: Lifetime = ron::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, lifetime);
+
+    // Also check that the reflected serialization is registered.
+    let registry = registry.read();
+    let registration = registry.get(std::any::TypeId::of::<Lifetime>()).unwrap();
+    let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+    let reflected = TypedReflectDeserializer::new(registration, &registry)
+        .deserialize(&mut deserializer)
+        .unwrap();
+    assert!(reflected.reflect_partial_eq(&lifetime).unwrap());
+}
+
+#[test]
+fn scene_round_trip() {
+    let registry = registry();
+
+    let mut world = World::new();
+    world.insert_resource(registry.clone());
+    init_effect_hook::<MyEffect>(&mut world);
+
+    let first_target = world.spawn_empty().id();
+    let second_target = world.spawn_empty().id();
+    let lifetime = Lifetime::from_seconds(2.0);
+
+    for target in [first_target, second_target] {
+        world.spawn((
+            MyEffect(1),
+            Effecting(target),
+            EffectMode::Replace,
+            lifetime.clone(),
+        ));
+    }
+    world.spawn((MyEffect(2), Effecting(first_target), EffectMode::Stack));
+    world.flush();
+
+    let scene = round_trip(&world, &registry);
+
+    let mut loaded = World::new();
+    loaded.insert_resource(registry.clone());
+    init_effect_hook::<MyEffect>(&mut loaded);
+
+    // An existing effect with the same type shouldn't be replaced, as it has a different target.
+    let existing_target = loaded.spawn_empty().id();
+    let existing = loaded
+        .spawn((MyEffect(3), Effecting(existing_target), EffectMode::Replace))
+        .id();
+
+    let mut entity_map = EntityHashMap::default();
+    scene.write_to_world(&mut loaded, &mut entity_map).unwrap();
+    loaded.flush();
+
+    assert_eq!(loaded.get::<MyEffect>(existing), Some(&MyEffect(3)));
+
+    for target in [first_target, second_target] {
+        let target = entity_map[&target];
+        let effects: Vec<Entity> = loaded
+            .get::<EffectedBy>(target)
+            .unwrap()
+            .into_iter()
+            .copied()
+            .collect();
+
+        let expected = if target == entity_map[&first_target] {
+            2
+        } else {
+            1
+        };
+        assert_eq!(effects.len(), expected);
+
+        for effect in effects {
+            assert_eq!(loaded.get::<Effecting>(effect), Some(&Effecting(target)));
+
+            if loaded.get::<EffectMode>(effect) == Some(&EffectMode::Replace) {
+                assert_eq!(loaded.get::<MyEffect>(effect), Some(&MyEffect(1)));
+                assert_eq!(loaded.get::<Lifetime>(effect), Some(&lifetime));
+            }
+        }
+    }
+}
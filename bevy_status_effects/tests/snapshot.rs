@@ -0,0 +1,133 @@
+//! Tests for snapshotting data from an effect's source when it is applied.
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::{Reflect, TypeRegistry};
+use bevy_status_effects::*;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Burning;
+
+#[derive(Component, Reflect, Eq, PartialEq, Debug, Clone)]
+struct SpellPower(u32);
+
+#[derive(Component, Eq, PartialEq, Debug, Clone)]
+struct Level(u32);
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+struct Damage(u32);
+
+fn world(mode: SnapshotMergeMode) -> World {
+    let mut world = World::new();
+    init_effect_hook::<Burning>(&mut world);
+    register_snapshot::<SpellPower>(&mut world, mode);
+    register_computed_snapshot(&mut world, mode, |source| {
+        let power = source.get::<SpellPower>()?;
+        let level = source.get::<Level>()?;
+        Some(Damage(power.0 * level.0))
+    });
+    world
+}
+
+#[test]
+fn taken_on_apply() {
+    let mut world = world(SnapshotMergeMode::Replace);
+    let caster = world.spawn((SpellPower(10), Level(3))).id();
+    let target = world.spawn_empty().id();
+
+    let effect = apply_effect(&mut world, target, (Burning, EffectSource(caster))).unwrap();
+
+    // Changing the source doesn't change the snapshot.
+    world.get_mut::<SpellPower>(caster).unwrap().0 = 50;
+
+    assert_eq!(
+        world.get::<Snapshot<SpellPower>>(effect),
+        Some(&Snapshot(SpellPower(10)))
+    );
+    assert_eq!(
+        world.get::<Snapshot<Damage>>(effect),
+        Some(&Snapshot(Damage(30)))
+    );
+}
+
+#[test]
+fn missing_data() {
+    let mut world = world(SnapshotMergeMode::Replace);
+    let caster = world.spawn(SpellPower(10)).id();
+    let target = world.spawn_empty().id();
+
+    let effect = apply_effect(&mut world, target, (Burning, EffectSource(caster))).unwrap();
+    let unsourced = apply_effect(&mut world, target, Burning).unwrap();
+
+    assert!(world.get::<Snapshot<SpellPower>>(effect).is_some());
+    assert!(world.get::<Snapshot<Damage>>(effect).is_none());
+    assert!(world.get::<Snapshot<SpellPower>>(unsourced).is_none());
+}
+
+fn replace(mode: SnapshotMergeMode) -> Snapshot<SpellPower> {
+    let mut world = world(mode);
+    let weak = world.spawn(SpellPower(5)).id();
+    let strong = world.spawn(SpellPower(20)).id();
+    let target = world.spawn_empty().id();
+
+    apply_effect(
+        &mut world,
+        target,
+        (Burning, EffectMode::Replace, EffectSource(strong)),
+    )
+    .unwrap();
+    let effect = apply_effect(
+        &mut world,
+        target,
+        (Burning, EffectMode::Replace, EffectSource(weak)),
+    )
+    .unwrap();
+    world.flush();
+
+    assert_eq!(world.get::<EffectedBy>(target).unwrap().len(), 1);
+    world.get::<Snapshot<SpellPower>>(effect).unwrap().clone()
+}
+
+#[test]
+fn merge_modes() {
+    assert_eq!(replace(SnapshotMergeMode::Replace), Snapshot(SpellPower(5)));
+    assert_eq!(
+        replace(SnapshotMergeMode::Inherit),
+        Snapshot(SpellPower(20))
+    );
+}
+
+#[test]
+fn not_taken_when_spawned() {
+    let mut world = world(SnapshotMergeMode::Replace);
+    let caster = world.spawn(SpellPower(10)).id();
+    let target = world.spawn_empty().id();
+
+    // Only `apply_effect` takes snapshots.
+    let effect = world
+        .spawn((Burning, EffectSource(caster), Effecting(target)))
+        .id();
+    world.flush();
+
+    assert!(world.get::<Snapshot<SpellPower>>(effect).is_none());
+}
+
+#[test]
+fn reflect() {
+    let mut world = world(SnapshotMergeMode::Replace);
+    let caster = world.spawn(SpellPower(10)).id();
+    let target = world.spawn_empty().id();
+    let effect = apply_effect(&mut world, target, (Burning, EffectSource(caster))).unwrap();
+
+    let mut type_registry = TypeRegistry::new();
+    type_registry.register::<Snapshot<SpellPower>>();
+
+    let reflect_component = type_registry
+        .get_type_data::<ReflectComponent>(std::any::TypeId::of::<Snapshot<SpellPower>>())
+        .unwrap();
+    let snapshot = reflect_component
+        .reflect(world.entity(effect))
+        .unwrap()
+        .downcast_ref::<Snapshot<SpellPower>>();
+
+    assert_eq!(snapshot, Some(&Snapshot(SpellPower(10))));
+}
@@ -0,0 +1,198 @@
+//! Tests for aggregating stat modifiers from active effects.
+
+use bevy_app::{App, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypePath;
+use bevy_status_effects::*;
+
+#[derive(TypePath)]
+struct MoveSpeed;
+
+impl StatKey for MoveSpeed {}
+
+#[derive(TypePath)]
+struct Armor;
+
+impl StatKey for Armor {}
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Slowed;
+
+#[derive(Resource, Default)]
+struct Recomputed(usize);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        StatPlugin::<MoveSpeed>::default(),
+        StatPlugin::<Armor>::default(),
+    ))
+    .init_resource::<Recomputed>()
+    .add_systems(
+        PreUpdate,
+        (|stats: Query<(), Changed<StatValue<MoveSpeed>>>, mut recomputed: ResMut<Recomputed>| {
+            recomputed.0 += stats.iter().count();
+        })
+        .after(StatusEffectSystems::UpdateStats),
+    );
+    init_effect_hook::<Slowed>(app.world_mut());
+    app
+}
+
+fn move_speed(app: &App, target: Entity) -> f32 {
+    app.world()
+        .get::<StatValue<MoveSpeed>>(target)
+        .unwrap()
+        .value()
+}
+
+#[test]
+fn aggregate() {
+    let mut app = app();
+    let target = app
+        .world_mut()
+        .spawn(StatValue::<MoveSpeed>::new(10.0))
+        .id();
+
+    app.world_mut().spawn((
+        Slowed,
+        Effecting(target),
+        StatModifier::<MoveSpeed>::add(2.0),
+    ));
+    app.world_mut().spawn((
+        Slowed,
+        Effecting(target),
+        StatModifier::<MoveSpeed>::multiply(0.5),
+    ));
+    app.update();
+    assert_eq!(move_speed(&app, target), 6.0);
+
+    // Each stack contributes its own modifier.
+    let stack = app
+        .world_mut()
+        .spawn((
+            Slowed,
+            Effecting(target),
+            StatModifier::<MoveSpeed>::multiply(0.5),
+        ))
+        .id();
+    app.update();
+    assert_eq!(move_speed(&app, target), 3.0);
+
+    app.world_mut().despawn(stack);
+    app.update();
+    assert_eq!(move_speed(&app, target), 6.0);
+
+    // Modifiers for other stats are ignored.
+    app.world_mut()
+        .spawn((Slowed, Effecting(target), StatModifier::<Armor>::add(5.0)));
+    app.update();
+    assert_eq!(move_speed(&app, target), 6.0);
+}
+
+#[test]
+fn set() {
+    let mut app = app();
+    let target = app
+        .world_mut()
+        .spawn(StatValue::<MoveSpeed>::new(10.0))
+        .id();
+
+    app.world_mut().spawn((
+        Slowed,
+        Effecting(target),
+        StatModifier::<MoveSpeed>::add(2.0),
+    ));
+    let rooted = app
+        .world_mut()
+        .spawn((Effecting(target), StatModifier::<MoveSpeed>::set(0.0)))
+        .id();
+    app.world_mut()
+        .spawn((Effecting(target), StatModifier::<MoveSpeed>::set(1.0)));
+    app.update();
+    assert_eq!(move_speed(&app, target), 1.0);
+
+    app.world_mut()
+        .get_mut::<StatModifier<MoveSpeed>>(rooted)
+        .unwrap()
+        .set = None;
+    app.update();
+    assert_eq!(move_speed(&app, target), 1.0);
+}
+
+#[test]
+fn recomputed_on_change() {
+    let mut app = app();
+    let target = app
+        .world_mut()
+        .spawn(StatValue::<MoveSpeed>::new(10.0))
+        .id();
+    let effect = app
+        .world_mut()
+        .spawn((Effecting(target), StatModifier::<MoveSpeed>::add(1.0)))
+        .id();
+    app.update();
+    assert_eq!(move_speed(&app, target), 11.0);
+
+    // Nothing changed, so the value isn't written again.
+    let recomputed = app.world().resource::<Recomputed>().0;
+    app.update();
+    app.update();
+    assert_eq!(app.world().resource::<Recomputed>().0, recomputed);
+
+    app.world_mut()
+        .get_mut::<StatModifier<MoveSpeed>>(effect)
+        .unwrap()
+        .add = 4.0;
+    app.update();
+    assert_eq!(move_speed(&app, target), 14.0);
+
+    app.world_mut()
+        .get_mut::<StatValue<MoveSpeed>>(target)
+        .unwrap()
+        .base = 20.0;
+    app.update();
+    assert_eq!(move_speed(&app, target), 24.0);
+
+    // Removing the last effect removes `EffectedBy` entirely.
+    app.world_mut().despawn(effect);
+    app.update();
+    assert_eq!(move_speed(&app, target), 20.0);
+}
+
+#[test]
+fn nan_not_recomputed() {
+    let mut app = app();
+    let target = app
+        .world_mut()
+        .spawn(StatValue::<MoveSpeed>::new(10.0))
+        .id();
+    app.world_mut().spawn((
+        Effecting(target),
+        StatModifier::<MoveSpeed>::multiply(f32::NAN),
+    ));
+    app.update();
+    assert!(move_speed(&app, target).is_nan());
+
+    let recomputed = app.world().resource::<Recomputed>().0;
+    app.update();
+    app.update();
+    assert_eq!(app.world().resource::<Recomputed>().0, recomputed);
+}
+
+#[test]
+fn reflect() {
+    let app = app();
+    let type_registry = app.world().resource::<AppTypeRegistry>().read();
+
+    assert!(
+        type_registry
+            .get_type_data::<ReflectComponent>(std::any::TypeId::of::<StatModifier<MoveSpeed>>())
+            .is_some()
+    );
+    assert!(
+        type_registry
+            .get_type_data::<ReflectComponent>(std::any::TypeId::of::<StatValue<Armor>>())
+            .is_some()
+    );
+}
@@ -0,0 +1,115 @@
+//! Tests for deterministic, simulation tick based effect timers.
+
+use bevy_app::{App, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use bevy_time::Time;
+
+#[derive(StatusEffect, Component, Debug, Eq, PartialEq, Default)]
+struct MyEffect;
+
+#[test]
+fn lifetime() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::default());
+    app.init_resource::<Time>();
+
+    let effect = app.world_mut().spawn(TickLifetime::new(3)).id();
+
+    // Timers shouldn't tick unless the simulation does.
+    app.world_mut().run_schedule(PreUpdate);
+    assert_eq!(
+        app.world()
+            .get::<TickLifetime>(effect)
+            .unwrap()
+            .timer
+            .elapsed(),
+        0
+    );
+
+    app.world_mut().resource_mut::<SimulationTick>().advance();
+    app.world_mut().run_schedule(PreUpdate);
+    assert_eq!(
+        app.world()
+            .get::<TickLifetime>(effect)
+            .unwrap()
+            .timer
+            .elapsed(),
+        1
+    );
+
+    app.world_mut()
+        .resource_mut::<SimulationTick>()
+        .advance_by(2);
+    app.world_mut().run_schedule(PreUpdate);
+    assert!(app.world().get_entity(effect).is_err());
+}
+
+#[test]
+fn delay() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::default());
+    app.init_resource::<Time>();
+
+    let effect = app.world_mut().spawn(TickDelay::new(2)).id();
+
+    app.world_mut()
+        .resource_mut::<SimulationTick>()
+        .advance_by(5);
+    app.world_mut().run_schedule(PreUpdate);
+
+    let delay = app.world().get::<TickDelay>(effect).unwrap();
+    assert_eq!(delay.timer.times_finished_this_tick(), 2);
+    assert_eq!(delay.timer.elapsed(), 1);
+}
+
+#[test]
+fn delay_without_step() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::default());
+    app.init_resource::<Time>();
+
+    let effect = app.world_mut().spawn(TickDelay::new(1)).id();
+
+    let just_finished = |app: &App| {
+        app.world()
+            .get::<TickDelay>(effect)
+            .unwrap()
+            .timer
+            .just_finished()
+    };
+
+    app.world_mut().resource_mut::<SimulationTick>().advance();
+    app.world_mut().run_schedule(PreUpdate);
+    assert!(just_finished(&app));
+
+    app.world_mut().run_schedule(PreUpdate);
+    assert!(!just_finished(&app));
+}
+
+#[test]
+fn merge_fraction() {
+    let mut world = World::new();
+    init_effect_hook::<MyEffect>(&mut world);
+
+    let target = world.spawn_empty().id();
+
+    let mut first = TickDelay::new(3).with_mode(TimerMergeMode::Fraction);
+    first.timer.tick(1);
+
+    world.spawn((MyEffect, Effecting(target), EffectMode::Replace, first));
+    let second = world
+        .spawn((
+            MyEffect,
+            Effecting(target),
+            EffectMode::Replace,
+            TickDelay::new(u32::MAX).with_mode(TimerMergeMode::Fraction),
+        ))
+        .id();
+
+    world.flush();
+
+    // Integer math always rounds down, and doesn't overflow.
+    let delay = world.get::<TickDelay>(second).unwrap();
+    assert_eq!(delay.timer.elapsed(), u32::MAX / 3);
+}
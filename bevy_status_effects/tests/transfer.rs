@@ -0,0 +1,190 @@
+//! Tests for transferring effects between targets.
+
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+use std::time::Duration;
+
+#[derive(StatusEffect, Component, Debug, Eq, PartialEq, Default, Clone)]
+struct Poisoned(u32);
+
+#[derive(StatusEffect, Component, Debug, Eq, PartialEq, Default, Clone)]
+struct Blessed;
+
+#[derive(Resource, Default)]
+struct Transferred(Vec<EffectTransferred>);
+
+fn world() -> World {
+    let mut world = World::new();
+    init_effect_hook::<Poisoned>(&mut world);
+    init_effect_hook::<Blessed>(&mut world);
+    world.init_resource::<Transferred>();
+    world.add_observer(
+        |trigger: Trigger<EffectTransferred>, mut transferred: ResMut<Transferred>| {
+            transferred.0.push(*trigger.event());
+        },
+    );
+    world
+}
+
+#[test]
+fn move_replaces() {
+    let mut world = world();
+    let player = world.spawn_empty().id();
+    let enemy = world.spawn_empty().id();
+
+    let mut lifetime = Lifetime::from_seconds(10.0).with_mode(TimerMergeMode::Inherit);
+    lifetime.timer.tick(Duration::from_secs(2));
+    let effect = world
+        .spawn((
+            Effecting(player),
+            EffectMode::Replace,
+            Poisoned(5),
+            lifetime,
+        ))
+        .id();
+    let existing = world
+        .spawn((
+            Effecting(enemy),
+            EffectMode::Replace,
+            Poisoned(1),
+            Lifetime::from_seconds(10.0),
+        ))
+        .id();
+    world
+        .get_mut::<Lifetime>(existing)
+        .unwrap()
+        .timer
+        .tick(Duration::from_secs(6));
+    world.flush();
+
+    let moved = transfer_effect(&mut world, effect, enemy, TransferMode::Move).unwrap();
+
+    assert_eq!(moved, effect);
+    assert!(world.get_entity(existing).is_err());
+    assert!(world.get::<EffectedBy>(player).is_none());
+    assert_eq!(world.get::<EffectedBy>(enemy).unwrap().len(), 1);
+    assert_eq!(
+        world.get::<Lifetime>(effect).unwrap().timer.elapsed(),
+        Duration::from_secs(6)
+    );
+    assert_eq!(
+        world.resource::<Transferred>().0,
+        vec![EffectTransferred {
+            original: effect,
+            effect,
+            from: player,
+            to: enemy,
+        }]
+    );
+
+    // The effect is no longer indexed on the old target, so it isn't replaced by new effects there.
+    let new = world
+        .spawn((Effecting(player), EffectMode::Replace, Poisoned(3)))
+        .id();
+    world.flush();
+    assert!(world.get_entity(effect).is_ok());
+    assert!(world.get_entity(new).is_ok());
+}
+
+#[test]
+fn clone() {
+    let mut world = world();
+    let player = world.spawn_empty().id();
+    let ally = world.spawn_empty().id();
+
+    let effect = world.spawn((Effecting(player), Blessed)).id();
+
+    let cloned = transfer_effect(&mut world, effect, ally, TransferMode::Clone).unwrap();
+
+    assert_ne!(cloned, effect);
+    assert_eq!(world.get::<Effecting>(effect), Some(&Effecting(player)));
+    assert_eq!(world.get::<Effecting>(cloned), Some(&Effecting(ally)));
+    assert_eq!(world.get::<Blessed>(cloned), Some(&Blessed));
+    assert_eq!(world.resource::<Transferred>().0[0].original, effect);
+    assert_eq!(world.resource::<Transferred>().0[0].effect, cloned);
+}
+
+#[test]
+fn filtered() {
+    let mut world = world();
+    let player = world.spawn_empty().id();
+    let enemy = world.spawn_empty().id();
+
+    let poisoned = world.spawn((Effecting(player), Poisoned(1))).id();
+    let blessed = world.spawn((Effecting(player), Blessed)).id();
+
+    let moved = transfer_effects(&mut world, player, enemy, TransferMode::Move, |effect| {
+        effect.contains::<Poisoned>()
+    })
+    .unwrap();
+
+    assert_eq!(moved, vec![poisoned]);
+    assert_eq!(world.get::<Effecting>(poisoned), Some(&Effecting(enemy)));
+    assert_eq!(world.get::<Effecting>(blessed), Some(&Effecting(player)));
+}
+
+#[test]
+fn errors() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+    let not_effect = world.spawn_empty().id();
+    let missing = world.spawn_empty().id();
+    world.despawn(missing);
+
+    assert!(matches!(
+        transfer_effect(&mut world, not_effect, target, TransferMode::Move),
+        Err(StatusEffectError::InvalidEffect(_))
+    ));
+    assert!(matches!(
+        transfer_effect(&mut world, missing, target, TransferMode::Move),
+        Err(StatusEffectError::InvalidEffect(_))
+    ));
+
+    let effect = world.spawn((Effecting(target), Blessed)).id();
+    assert!(matches!(
+        transfer_effect(&mut world, effect, missing, TransferMode::Move),
+        Err(StatusEffectError::InvalidTarget(_))
+    ));
+}
+
+#[test]
+fn rejected_by_target() {
+    let mut world = world();
+    let from = world.spawn_empty().id();
+    let immune = world
+        .spawn(EffectImmunity([EffectKey::of::<Blessed>()].into()))
+        .id();
+    let effect = world.spawn((Effecting(from), Blessed)).id();
+
+    assert_eq!(
+        transfer_effect(&mut world, effect, immune, TransferMode::Move),
+        Err(StatusEffectError::Immune {
+            target: immune,
+            key: EffectKey::of::<Blessed>()
+        })
+    );
+    assert_eq!(world.get::<Effecting>(effect), Some(&Effecting(from)));
+}
+
+#[test]
+fn cancelled() {
+    let mut world = world();
+    let from = world.spawn_empty().id();
+    let to = world.spawn_empty().id();
+    world
+        .entity_mut(to)
+        .observe(|mut trigger: Trigger<ApplyingEffect>| {
+            trigger.event_mut().cancel("warded");
+        });
+    let effect = world.spawn((Effecting(from), Blessed)).id();
+
+    assert!(matches!(
+        transfer_effect(&mut world, effect, to, TransferMode::Move),
+        Err(StatusEffectError::Cancelled { .. })
+    ));
+    assert_eq!(world.get::<Effecting>(effect), Some(&Effecting(from)));
+
+    let entities = world.entities().len();
+    assert!(transfer_effect(&mut world, effect, to, TransferMode::Clone).is_err());
+    assert_eq!(world.entities().len(), entities);
+}
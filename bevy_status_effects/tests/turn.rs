@@ -0,0 +1,112 @@
+//! Tests for turn-based effect timers.
+
+use bevy_app::App;
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+
+#[derive(StatusEffect, Component, Debug, Eq, PartialEq, Default)]
+struct MyEffect;
+
+#[test]
+fn lifetime() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::default());
+
+    let target = app.world_mut().spawn_empty().id();
+    let effect = app
+        .world_mut()
+        .spawn((MyEffect, Effecting(target), TurnLifetime::new(2)))
+        .id();
+
+    app.world_mut().trigger(AdvanceTurn);
+    app.world_mut().flush();
+    assert!(app.world().get_entity(effect).is_ok());
+
+    app.world_mut().trigger(AdvanceTurn);
+    app.world_mut().flush();
+    assert!(app.world().get_entity(effect).is_err());
+}
+
+#[test]
+fn targeted() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::default());
+
+    let first_target = app.world_mut().spawn_empty().id();
+    let second_target = app.world_mut().spawn_empty().id();
+
+    let first = app
+        .world_mut()
+        .spawn((MyEffect, Effecting(first_target), TurnLifetime::new(1)))
+        .id();
+    let second = app
+        .world_mut()
+        .spawn((MyEffect, Effecting(second_target), TurnLifetime::new(1)))
+        .id();
+
+    app.world_mut().trigger_targets(AdvanceTurn, first_target);
+    app.world_mut().flush();
+
+    assert!(app.world().get_entity(first).is_err());
+    assert!(app.world().get_entity(second).is_ok());
+}
+
+#[test]
+fn delay() {
+    let mut app = App::new();
+    app.add_plugins(StatusEffectPlugin::default());
+
+    let target = app.world_mut().spawn_empty().id();
+    let effect = app
+        .world_mut()
+        .spawn((MyEffect, Effecting(target), TurnDelay::new(2)))
+        .id();
+
+    let mut finished = Vec::new();
+    for _ in 0..4 {
+        app.world_mut().trigger_targets(AdvanceTurn, target);
+        let delay = app.world().get::<TurnDelay>(effect).unwrap();
+        finished.push(delay.timer.just_finished());
+    }
+
+    assert_eq!(finished, [false, true, false, true]);
+}
+
+#[test]
+fn merge_fraction() {
+    let mut world = World::new();
+    init_effect_hook::<MyEffect>(&mut world);
+
+    let target = world.spawn_empty().id();
+
+    let mut first = TurnLifetime::new(4).with_mode(TimerMergeMode::Fraction);
+    first.timer.tick(3);
+
+    world.spawn((MyEffect, Effecting(target), EffectMode::Replace, first));
+    let second = world
+        .spawn((
+            MyEffect,
+            Effecting(target),
+            EffectMode::Replace,
+            TurnLifetime::new(10).with_mode(TimerMergeMode::Fraction),
+        ))
+        .id();
+
+    world.flush();
+
+    let lifetime = world.get::<TurnLifetime>(second).unwrap();
+    assert_eq!(lifetime.timer.elapsed(), 7);
+    assert_eq!(lifetime.timer.remaining(), 3);
+}
+
+#[test]
+fn merge_max() {
+    let mut first = TurnLifetime::new(3).with_mode(TimerMergeMode::Max);
+    first.timer.tick(2);
+    let second = TurnLifetime::new(2).with_mode(TimerMergeMode::Max);
+
+    let mut result = second.clone();
+    result.merge(&first);
+
+    assert_eq!(result, second);
+}
@@ -0,0 +1,124 @@
+//! Tests for validating and repairing effect invariants.
+
+use bevy_ecs::prelude::*;
+use bevy_status_effects::*;
+
+#[derive(StatusEffect, Component, Debug, Default)]
+struct Stunned;
+
+fn world() -> World {
+    let mut world = World::new();
+    init_effect_hook::<Stunned>(&mut world);
+    world
+}
+
+#[test]
+fn valid() {
+    let mut world = world();
+    let target = world.spawn_empty().id();
+    world.spawn((
+        Stunned,
+        Effecting(target),
+        EffectMode::Replace,
+        Lifetime::from_seconds(1.0),
+    ));
+    world.spawn((Stunned, Effecting(target), EffectMode::Replace));
+    world.spawn((Stunned, Effecting(target)));
+    world.spawn((Stunned, Effecting(target)));
+    world.flush();
+
+    assert_eq!(validate_effects(&world), vec![]);
+}
+
+#[test]
+fn duplicate_replace() {
+    // The effects are spawned while the index is missing, so neither effect is replaced.
+    let mut world = world();
+    world.remove_resource::<EffectIndex>();
+    let target = world.spawn_empty().id();
+    let first = world
+        .spawn((Stunned, Effecting(target), EffectMode::Replace))
+        .id();
+    let second = world
+        .spawn((Stunned, Effecting(target), EffectMode::Replace))
+        .id();
+    world.init_resource::<EffectIndex>();
+    world.flush();
+
+    let violations = validate_effects(&world);
+    assert!(matches!(
+        violations.as_slice(),
+        [EffectViolation::DuplicateReplace { target: t, effects, .. }] if *t == target && *effects == vec![first, second]
+    ));
+
+    repair_effect_violations(&mut world, &violations);
+    assert!(world.get_entity(first).is_err());
+    assert_eq!(validate_effects(&world), vec![]);
+
+    // The remaining effect is indexed again, so it is replaced by new effects.
+    let third = world
+        .spawn((Stunned, Effecting(target), EffectMode::Replace))
+        .id();
+    world.flush();
+    assert!(world.get_entity(second).is_err());
+    assert!(world.get_entity(third).is_ok());
+}
+
+#[test]
+fn repair_keeps_highest_sequence() {
+    let mut world = world();
+    world.remove_resource::<EffectIndex>();
+    let target = world.spawn_empty().id();
+    let first = world
+        .spawn((
+            Stunned,
+            Effecting(target),
+            EffectMode::Replace,
+            EffectSequence(10),
+        ))
+        .id();
+    let second = world
+        .spawn((
+            Stunned,
+            Effecting(target),
+            EffectMode::Replace,
+            EffectSequence(5),
+        ))
+        .id();
+    world.init_resource::<EffectIndex>();
+    world.flush();
+
+    // The first effect was applied first, but has the higher sequence number, so it is kept.
+    let violations = validate_effects(&world);
+    repair_effect_violations(&mut world, &violations);
+    assert!(world.get_entity(first).is_ok());
+    assert!(world.get_entity(second).is_err());
+    assert_eq!(validate_effects(&world), vec![]);
+}
+
+#[test]
+fn missing_target() {
+    let mut world = world();
+    let effect = world.spawn((Stunned, Lifetime::from_seconds(1.0))).id();
+
+    let violations = validate_effects(&world);
+    assert_eq!(violations, vec![EffectViolation::MissingTarget(effect)]);
+
+    repair_effect_violations(&mut world, &violations);
+    assert!(world.get_entity(effect).is_err());
+}
+
+#[test]
+fn system() {
+    let mut world = world();
+    world.insert_resource(EffectValidation { repair: true });
+    let effect = world.spawn((Stunned, Lifetime::from_seconds(1.0))).id();
+
+    world.run_system_cached(validate_effects_system).unwrap();
+
+    assert_eq!(
+        world.resource::<EffectViolations>().0,
+        vec![EffectViolation::MissingTarget(effect)]
+    );
+    assert!(world.get_entity(effect).is_err());
+}
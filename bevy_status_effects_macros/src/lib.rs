@@ -1,18 +1,53 @@
 #[cfg(feature = "bevy_butler")]
 mod bevy_butler;
 
+use darling::FromDeriveInput;
 use proc_macro_error::proc_macro_error;
 use quote::quote;
 use syn::{DeriveInput, parse_macro_input};
 
-#[proc_macro_derive(StatusEffect, attributes(add_component))]
+/// The `#[status_effect(...)]` attribute.
+#[derive(FromDeriveInput)]
+#[darling(attributes(status_effect))]
+struct StatusEffectAttributes {
+    /// A stable identifier, which is used instead of the type path.
+    id: Option<String>,
+    /// The current version of the effect's data, used when migrating old saves.
+    version: Option<u32>,
+}
+
+#[proc_macro_derive(StatusEffect, attributes(add_component, status_effect))]
 #[proc_macro_error]
 pub fn stat_container_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let tree: DeriveInput = parse_macro_input!(item as DeriveInput);
     let ident = &tree.ident;
 
+    let attributes = match StatusEffectAttributes::from_derive_input(&tree) {
+        Ok(attributes) => attributes,
+        Err(error) => return error.write_errors().into(),
+    };
+
+    let id = attributes.id.map(|id| {
+        quote! {
+            fn id() -> Option<bevy_status_effects::EffectId> {
+                Some(bevy_status_effects::EffectId::new(#id))
+            }
+        }
+    });
+
+    let version = attributes.version.map(|version| {
+        quote! {
+            fn version() -> u32 {
+                #version
+            }
+        }
+    });
+
     let trait_impl = quote! {
-        impl StatusEffect for #ident {}
+        impl StatusEffect for #ident {
+            #id
+            #version
+        }
     };
 
     #[cfg(feature = "bevy_butler")]